@@ -19,9 +19,7 @@ fn compute(input: String) -> String {
         .filter_map(|line| {
             let game = Game::from_str(line).unwrap();
 
-            let max_pulls = game.rounds.iter().fold(Cubes::default(), |a, b| a.max(b));
-
-            if max_pulls.is_subset_of(&limits) {
+            if game.fits(&limits) {
                 Some(game.id)
             } else {
                 None
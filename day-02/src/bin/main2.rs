@@ -1,4 +1,4 @@
-use day_02::{Cubes, Game};
+use day_02::Game;
 use std::fs;
 use std::str::FromStr;
 
@@ -13,8 +13,7 @@ fn compute(input: String) -> String {
         .map(|line| {
             let game = Game::from_str(line).unwrap();
 
-            let max_pulls = game.rounds.iter().fold(Cubes::default(), |a, b| a.max(b));
-            max_pulls.power()
+            game.minimal_bag().power()
         })
         .sum::<usize>()
         .to_string()
@@ -0,0 +1,60 @@
+use day_02::{distribution, estimate_bag, feasible_games, parse_games, smallest_common_bag, Cubes};
+use std::{env, fs};
+
+const USAGE: &str =
+    "usage: query <feasible RED,GREEN,BLUE... | smallest | distribution | estimate TOTAL>";
+
+fn main() {
+    let input = fs::read_to_string("inputs/input1.txt").unwrap();
+    let games = parse_games(&input).unwrap();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("feasible") => {
+            let bags: Vec<Cubes> = args[1..].iter().map(|arg| parse_bag(arg)).collect();
+            for (bag, ids) in bags.iter().zip(feasible_games(&games, &bags)) {
+                println!(
+                    "{},{},{}: {} games, id sum {}",
+                    bag.red,
+                    bag.green,
+                    bag.blue,
+                    ids.len(),
+                    ids.iter().sum::<usize>()
+                );
+            }
+        }
+        Some("smallest") => {
+            let bag = smallest_common_bag(&games);
+            println!("red {}, green {}, blue {}", bag.red, bag.green, bag.blue);
+        }
+        Some("distribution") => {
+            let dist = distribution(&games);
+            for (color, counts) in [
+                ("red", dist.red),
+                ("green", dist.green),
+                ("blue", dist.blue),
+            ] {
+                println!("{}:", color);
+                for (drawn, times) in counts {
+                    println!("  {:>3} drawn {} times", drawn, times);
+                }
+            }
+        }
+        Some("estimate") => {
+            let total = args.get(1).and_then(|arg| arg.parse().ok()).expect(USAGE);
+            match estimate_bag(&games, total) {
+                Some(bag) => println!("red {}, green {}, blue {}", bag.red, bag.green, bag.blue),
+                None => println!("No bag of {} cubes can produce these games", total),
+            }
+        }
+        _ => println!("{}", USAGE),
+    }
+}
+
+fn parse_bag(arg: &str) -> Cubes {
+    let counts: Vec<usize> = arg.split(',').map(|n| n.parse().expect(USAGE)).collect();
+    match counts[..] {
+        [red, green, blue] => Cubes { red, green, blue },
+        _ => panic!("{}", USAGE),
+    }
+}
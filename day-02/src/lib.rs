@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 #[derive(Debug)]
@@ -12,10 +13,10 @@ impl FromStr for Game {
         let rest = s.strip_prefix("Game ").ok_or(GameParseError)?;
         let id = rest
             .chars()
-            .take_while(|c| c.is_digit(10))
+            .take_while(|c| c.is_ascii_digit())
             .collect::<String>()
             .parse::<usize>()
-            .unwrap();
+            .map_err(|_| GameParseError)?;
 
         let prefix = format!("{}: ", id);
         let rounds = rest
@@ -23,27 +24,44 @@ impl FromStr for Game {
             .ok_or(GameParseError)?
             .split("; ")
             .map(|round| {
-                let draws = round.split(", ").map(|draw| draw.split_once(" ").unwrap());
+                let draws = round
+                    .split(", ")
+                    .map(|draw| draw.split_once(" ").ok_or(GameParseError))
+                    .collect::<Result<Vec<_>, _>>()?;
 
                 let get_draw = |color: &str| {
                     draws
-                        .clone()
+                        .iter()
                         .find_map(|draw| if draw.1 == color { Some(draw.0) } else { None })
                         .and_then(|val| val.parse::<usize>().ok())
                         .unwrap_or(0)
                 };
 
-                Cubes {
+                Ok(Cubes {
                     red: get_draw("red"),
                     blue: get_draw("blue"),
                     green: get_draw("green"),
-                }
+                })
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         Ok(Game { id, rounds })
     }
 }
+impl Game {
+    /// Smallest bag that could have produced every round of this game
+    pub fn minimal_bag(&self) -> Cubes {
+        self.rounds.iter().fold(Cubes::default(), |a, b| a.max(b))
+    }
+
+    pub fn fits(&self, bag: &Cubes) -> bool {
+        self.minimal_bag().is_subset_of(bag)
+    }
+}
+
+pub fn parse_games(input: &str) -> Result<Vec<Game>, GameParseError> {
+    input.lines().map(Game::from_str).collect()
+}
 
 #[derive(Debug)]
 pub struct GameParseError;
@@ -69,3 +87,152 @@ impl Cubes {
         self.red * self.green * self.blue
     }
 }
+
+/// Ids of the games that could have been played with each of the bags
+pub fn feasible_games(games: &[Game], bags: &[Cubes]) -> Vec<Vec<usize>> {
+    bags.iter()
+        .map(|bag| {
+            games
+                .iter()
+                .filter(|game| game.fits(bag))
+                .map(|game| game.id)
+                .collect()
+        })
+        .collect()
+}
+
+/// Smallest single bag that all of the games could have been played with
+pub fn smallest_common_bag(games: &[Game]) -> Cubes {
+    games
+        .iter()
+        .fold(Cubes::default(), |a, game| a.max(&game.minimal_bag()))
+}
+
+/// How many times each draw size was seen, per colour
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Distribution {
+    pub red: BTreeMap<usize, usize>,
+    pub green: BTreeMap<usize, usize>,
+    pub blue: BTreeMap<usize, usize>,
+}
+
+pub fn distribution(games: &[Game]) -> Distribution {
+    let mut dist = Distribution::default();
+
+    for round in games.iter().flat_map(|game| game.rounds.iter()) {
+        *dist.red.entry(round.red).or_default() += 1;
+        *dist.green.entry(round.green).or_default() += 1;
+        *dist.blue.entry(round.blue).or_default() += 1;
+    }
+
+    dist
+}
+
+/// Most likely bag with `total` cubes in it, assuming every round draws
+/// cubes without replacement and puts them back before the next round.
+///
+/// Returns None if no bag of that size can produce all of the rounds.
+pub fn estimate_bag(games: &[Game], total: usize) -> Option<Cubes> {
+    let minimal = smallest_common_bag(games);
+    if minimal.red + minimal.green + minimal.blue > total {
+        return None;
+    }
+
+    // ln(n!) for every n we could need
+    let ln_fact = (1..=total).fold(vec![0.0f64], |mut acc, n| {
+        acc.push(acc[n - 1] + (n as f64).ln());
+        acc
+    });
+    let ln_choose = |n: usize, k: usize| ln_fact[n] - ln_fact[k] - ln_fact[n - k];
+
+    let rounds: Vec<&Cubes> = games.iter().flat_map(|game| game.rounds.iter()).collect();
+
+    let mut best: Option<(f64, Cubes)> = None;
+    for red in minimal.red..=(total - minimal.green - minimal.blue) {
+        for green in minimal.green..=(total - red - minimal.blue) {
+            let bag = Cubes {
+                red,
+                green,
+                blue: total - red - green,
+            };
+
+            // Multivariate hypergeometric log-likelihood of all rounds
+            let likelihood = rounds
+                .iter()
+                .map(|round| {
+                    ln_choose(bag.red, round.red)
+                        + ln_choose(bag.green, round.green)
+                        + ln_choose(bag.blue, round.blue)
+                        - ln_choose(total, round.red + round.green + round.blue)
+                })
+                .sum::<f64>();
+
+            if best.as_ref().is_none_or(|(old, _)| likelihood > *old) {
+                best = Some((likelihood, bag));
+            }
+        }
+    }
+
+    best.map(|(_, bag)| bag)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_feasible_games() {
+        let games = parse_games(&fs::read_to_string("inputs/example1.txt").unwrap()).unwrap();
+        let bags = [
+            Cubes {
+                red: 12,
+                green: 13,
+                blue: 14,
+            },
+            Cubes::default(),
+        ];
+        assert_eq!(feasible_games(&games, &bags), vec![vec![1, 2, 5], vec![]]);
+    }
+
+    #[test]
+    fn test_smallest_common_bag() {
+        let games = parse_games(&fs::read_to_string("inputs/example1.txt").unwrap()).unwrap();
+        let bag = smallest_common_bag(&games);
+        assert_eq!((bag.red, bag.green, bag.blue), (20, 13, 15));
+    }
+
+    #[test]
+    fn test_distribution() {
+        let games = parse_games(&fs::read_to_string("inputs/example1.txt").unwrap()).unwrap();
+        let dist = distribution(&games);
+        assert_eq!(dist.red.values().sum::<usize>(), 14);
+        assert_eq!(dist.red.get(&1), Some(&4));
+        assert_eq!(dist.blue.get(&0), Some(&3));
+    }
+
+    #[test]
+    fn test_estimate_bag() {
+        let games = parse_games(&fs::read_to_string("inputs/example1.txt").unwrap()).unwrap();
+        assert!(estimate_bag(&games, 47).is_none());
+
+        let bag = estimate_bag(&games, 48).unwrap();
+        assert_eq!((bag.red, bag.green, bag.blue), (20, 13, 15));
+
+        let bag = estimate_bag(&games, 100).unwrap();
+        assert!(bag.is_subset_of(&Cubes {
+            red: 100,
+            green: 100,
+            blue: 100
+        }));
+        assert!(smallest_common_bag(&games).is_subset_of(&bag));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Game::from_str("Game 1: 3 blue, 4 red; 2 green").is_ok());
+        assert!(Game::from_str("Game x: 1 red").is_err());
+        assert!(Game::from_str("Game 1: red").is_err());
+        assert!(parse_games("Game 1: 1 red\nGame 2: 2blue").is_err());
+    }
+}
@@ -0,0 +1,30 @@
+use day_03::{graph::Graph, Schematic};
+use std::{env, fs};
+
+fn main() {
+    let input = fs::read_to_string("inputs/input.txt").unwrap();
+    let schematic = Schematic::new(&input);
+    let graph = Graph::new(&schematic);
+
+    match env::args().nth(1).as_deref() {
+        Some("dot") => println!("{}", graph.to_dot()),
+        Some("json") => println!("{}", graph.to_json()),
+        Some("stats") => {
+            let components = graph.components();
+            println!("{} components", components.len());
+            if let Some(largest) = components.first() {
+                println!(
+                    "Largest cluster: {} parts through {} symbols, part sum {}",
+                    largest.parts.len(),
+                    largest.symbols.len(),
+                    largest
+                        .parts
+                        .iter()
+                        .map(|&id| schematic.parts[id].value)
+                        .sum::<usize>()
+                );
+            }
+        }
+        _ => println!("usage: export <dot | json | stats>"),
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{Schematic, Symbol};
+use std::cmp::Reverse;
+
+/// Parts and symbols as nodes, with an edge wherever a symbol touches a part
+#[derive(Debug)]
+pub struct Graph<'a> {
+    schematic: &'a Schematic,
+    /// (symbol index, part index)
+    pub edges: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Component {
+    pub parts: Vec<usize>,
+    pub symbols: Vec<usize>,
+}
+
+impl<'a> Graph<'a> {
+    pub fn new(schematic: &'a Schematic) -> Graph<'a> {
+        let edges = schematic
+            .symbols
+            .iter()
+            .enumerate()
+            .flat_map(|(symbol, token)| {
+                schematic
+                    .adjacent_parts(token.pos)
+                    .into_iter()
+                    .map(move |part| (symbol, part))
+            })
+            .collect();
+
+        Graph { schematic, edges }
+    }
+
+    fn symbol_char(&self, symbol: usize) -> char {
+        match self.schematic.symbols[symbol].sym {
+            Symbol::Mark(kind) => kind,
+            Symbol::Value(_) => unreachable!("Values are stored as parts"),
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec!["graph schematic {".to_owned()];
+
+        for (id, part) in self.schematic.parts.iter().enumerate() {
+            lines.push(format!(
+                "    p{} [label=\"{}\" shape=box pos=\"{},{}!\"];",
+                id, part.value, part.pos.column, -part.pos.row
+            ));
+        }
+        for (id, token) in self.schematic.symbols.iter().enumerate() {
+            lines.push(format!(
+                "    s{} [label=\"{}\" shape=circle pos=\"{},{}!\"];",
+                id,
+                escape(self.symbol_char(id)),
+                token.pos.column,
+                -token.pos.row
+            ));
+        }
+        for (symbol, part) in &self.edges {
+            lines.push(format!("    s{} -- p{};", symbol, part));
+        }
+
+        lines.push("}".to_owned());
+        lines.join("\n")
+    }
+
+    pub fn to_json(&self) -> String {
+        let parts = self.schematic.parts.iter().enumerate().map(|(id, part)| {
+            format!(
+                "{{\"id\":\"p{}\",\"value\":{},\"row\":{},\"column\":{},\"length\":{}}}",
+                id, part.value, part.pos.row, part.pos.column, part.length
+            )
+        });
+        let symbols = self
+            .schematic
+            .symbols
+            .iter()
+            .enumerate()
+            .map(|(id, token)| {
+                format!(
+                    "{{\"id\":\"s{}\",\"symbol\":\"{}\",\"row\":{},\"column\":{}}}",
+                    id,
+                    escape(self.symbol_char(id)),
+                    token.pos.row,
+                    token.pos.column
+                )
+            });
+        let nodes = parts.chain(symbols).collect::<Vec<_>>().join(",");
+
+        let edges = self
+            .edges
+            .iter()
+            .map(|(symbol, part)| {
+                format!("{{\"source\":\"s{}\",\"target\":\"p{}\"}}", symbol, part)
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!("{{\"nodes\":[{}],\"edges\":[{}]}}", nodes, edges)
+    }
+
+    /// Connected components, largest part count first
+    pub fn components(&self) -> Vec<Component> {
+        // Union-find over parts followed by symbols
+        let part_count = self.schematic.parts.len();
+        let mut parent: Vec<usize> = (0..part_count + self.schematic.symbols.len()).collect();
+
+        fn root(parent: &mut [usize], mut node: usize) -> usize {
+            while parent[node] != node {
+                parent[node] = parent[parent[node]];
+                node = parent[node];
+            }
+            node
+        }
+
+        for (symbol, part) in &self.edges {
+            let a = root(&mut parent, *part);
+            let b = root(&mut parent, part_count + symbol);
+            parent[a] = b;
+        }
+
+        let mut components: Vec<Component> = vec![];
+        let mut slots = vec![None; parent.len()];
+        for node in 0..parent.len() {
+            let group = root(&mut parent, node);
+            let slot = *slots[group].get_or_insert_with(|| {
+                components.push(Component::default());
+                components.len() - 1
+            });
+
+            if node < part_count {
+                components[slot].parts.push(node);
+            } else {
+                components[slot].symbols.push(node - part_count);
+            }
+        }
+
+        components.sort_by_key(|component| Reverse(component.parts.len()));
+        components
+    }
+
+    /// Biggest group of parts joined together through shared symbols
+    pub fn largest_cluster(&self) -> Option<Component> {
+        self.components().into_iter().next()
+    }
+}
+
+/// Quotes and backslashes need escaping in both DOT and JSON strings
+fn escape(kind: char) -> String {
+    match kind {
+        '"' | '\\' => format!("\\{}", kind),
+        _ => kind.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_components() {
+        let schematic = Schematic::new("1.2.3\n.#.#.\n4....\n..*..\n....6");
+        let graph = Graph::new(&schematic);
+
+        assert_eq!(graph.edges, vec![(0, 0), (0, 1), (0, 3), (1, 1), (1, 2)]);
+
+        let components = graph.components();
+        assert_eq!(components.len(), 3);
+        assert_eq!(
+            components[0],
+            Component {
+                parts: vec![0, 1, 2, 3],
+                symbols: vec![0, 1]
+            }
+        );
+        assert_eq!(graph.largest_cluster(), Some(components[0].clone()));
+    }
+
+    #[test]
+    fn test_exports() {
+        let schematic = Schematic::new("12\n\"#");
+        let graph = Graph::new(&schematic);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("graph schematic {"));
+        assert!(dot.contains("s0 [label=\"\\\"\""));
+        assert!(dot.contains("s1 -- p0;"));
+
+        assert_eq!(
+            graph.to_json(),
+            concat!(
+                "{\"nodes\":[",
+                "{\"id\":\"p0\",\"value\":12,\"row\":0,\"column\":0,\"length\":2},",
+                "{\"id\":\"s0\",\"symbol\":\"\\\"\",\"row\":1,\"column\":0},",
+                "{\"id\":\"s1\",\"symbol\":\"#\",\"row\":1,\"column\":1}",
+                "],\"edges\":[",
+                "{\"source\":\"s0\",\"target\":\"p0\"},",
+                "{\"source\":\"s1\",\"target\":\"p0\"}",
+                "]}"
+            )
+        );
+    }
+}
@@ -0,0 +1,444 @@
+use std::collections::{BTreeMap, HashMap};
+
+pub mod graph;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbol {
+    Value(usize),
+    /// Any character that isn't a digit or '.'
+    Mark(char),
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Coord {
+    pub row: i32,
+    pub column: i32,
+}
+impl Coord {
+    pub fn new(row: usize, column: usize) -> Coord {
+        Coord {
+            row: row as i32,
+            column: column as i32,
+        }
+    }
+
+    /// The eight cells around this one
+    pub fn neighbours(self) -> impl Iterator<Item = Coord> {
+        (-1..=1)
+            .flat_map(|dr| (-1..=1).map(move |dc| (dr, dc)))
+            .filter(|&offset| offset != (0, 0))
+            .map(move |(dr, dc)| Coord {
+                row: self.row + dr,
+                column: self.column + dc,
+            })
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub sym: Symbol,
+    pub pos: Coord,
+    /// How many characters the token spans, digits included as written
+    pub length: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Part {
+    pub value: usize,
+    pub pos: Coord,
+    pub length: usize,
+}
+impl Part {
+    pub fn cells(&self) -> impl Iterator<Item = Coord> + '_ {
+        (0..self.length as i32).map(|offset| Coord {
+            row: self.pos.row,
+            column: self.pos.column + offset,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Schematic {
+    pub parts: Vec<Part>,
+    pub symbols: Vec<Token>,
+    /// Which part, if any, covers a cell
+    index: HashMap<Coord, usize>,
+}
+impl Schematic {
+    pub fn new(input: &str) -> Schematic {
+        let mut schematic = Schematic::default();
+
+        for token in input
+            .lines()
+            .enumerate()
+            .flat_map(|(line_number, line)| parse_line(line_number, line))
+        {
+            match token.sym {
+                Symbol::Value(value) => {
+                    let part = Part {
+                        value,
+                        pos: token.pos,
+                        length: token.length,
+                    };
+                    let id = schematic.parts.len();
+                    schematic.index.extend(part.cells().map(|cell| (cell, id)));
+                    schematic.parts.push(part);
+                }
+                _ => schematic.symbols.push(token),
+            }
+        }
+
+        schematic
+    }
+
+    pub fn part_at(&self, cell: Coord) -> Option<usize> {
+        self.index.get(&cell).copied()
+    }
+
+    /// Indices of the parts touching a cell, each part listed once
+    pub fn adjacent_parts(&self, cell: Coord) -> Vec<usize> {
+        let mut found: Vec<usize> = vec![];
+        for id in cell.neighbours().filter_map(|n| self.part_at(n)) {
+            if !found.contains(&id) {
+                found.push(id);
+            }
+        }
+        found
+    }
+
+    /// Whether each part touches at least one symbol
+    fn touched(&self) -> Vec<bool> {
+        let mut touched = vec![false; self.parts.len()];
+        for symbol in &self.symbols {
+            for id in self.adjacent_parts(symbol.pos) {
+                touched[id] = true;
+            }
+        }
+        touched
+    }
+
+    /// Parts touching at least one symbol, in reading order
+    pub fn symbol_adjacent_parts(&self) -> Vec<&Part> {
+        self.parts
+            .iter()
+            .zip(self.touched())
+            .filter_map(|(part, touched)| touched.then_some(part))
+            .collect()
+    }
+
+    /// Parts touching no symbol at all, in reading order
+    pub fn lone_parts(&self) -> Vec<&Part> {
+        self.parts
+            .iter()
+            .zip(self.touched())
+            .filter_map(|(part, touched)| (!touched).then_some(part))
+            .collect()
+    }
+
+    /// Every symbol with the parts around it, grouped by symbol character
+    pub fn connections(&self) -> BTreeMap<char, Vec<Connection>> {
+        let mut report: BTreeMap<char, Vec<Connection>> = BTreeMap::new();
+
+        for symbol in &self.symbols {
+            if let Symbol::Mark(kind) = symbol.sym {
+                report.entry(kind).or_default().push(Connection {
+                    pos: symbol.pos,
+                    parts: self.adjacent_parts(symbol.pos),
+                });
+            }
+        }
+
+        report
+    }
+
+    /// Sum of the products of part values around every `kind` symbol that
+    /// touches exactly `count` parts. Gear ratios are `ratio_sum('*', 2)`.
+    pub fn ratio_sum(&self, kind: char, count: usize) -> usize {
+        self.connections()
+            .get(&kind)
+            .into_iter()
+            .flatten()
+            .filter(|connection| connection.parts.len() == count)
+            .map(|connection| {
+                connection
+                    .parts
+                    .iter()
+                    .map(|&id| self.parts[id].value)
+                    .product::<usize>()
+            })
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Connection {
+    /// Where the symbol is
+    pub pos: Coord,
+    /// Indices of the parts around the symbol
+    pub parts: Vec<usize>,
+}
+
+pub fn parse_line(line_number: usize, line: &str) -> Vec<Token> {
+    let mut collector = vec![];
+
+    let mut digits = vec![];
+    // Counted in chars, like every other position
+    let mut width = 0;
+    for (char_number, next) in line.chars().enumerate() {
+        width = char_number + 1;
+        if next.is_ascii_digit() {
+            digits.push(next);
+            continue;
+        }
+
+        if !digits.is_empty() {
+            let length = digits.len();
+            let value = digits.drain(..).collect::<String>().parse().unwrap();
+
+            collector.push(Token {
+                sym: Symbol::Value(value),
+                pos: Coord::new(line_number, char_number - length),
+                length,
+            });
+        }
+
+        if next == '.' {
+            continue;
+        }
+        collector.push(Token {
+            sym: Symbol::Mark(next),
+            pos: Coord::new(line_number, char_number),
+            length: 1,
+        });
+    }
+
+    if !digits.is_empty() {
+        let length = digits.len();
+        let value = digits.drain(..).collect::<String>().parse().unwrap();
+
+        collector.push(Token {
+            sym: Symbol::Value(value),
+            pos: Coord::new(line_number, width - length),
+            length,
+        });
+    }
+
+    collector
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A schematic with a single part at 4, 4
+    fn single_part(digits: &str) -> Schematic {
+        let mut input = vec![String::new(); 4];
+        input.push(format!("....{}", digits));
+        Schematic::new(&input.join("\n"))
+    }
+
+    fn touches(schematic: &Schematic, row: usize, column: usize) -> bool {
+        !schematic.adjacent_parts(Coord::new(row, column)).is_empty()
+    }
+
+    #[test]
+    fn test_part_lengths() {
+        for (digits, length) in [
+            ("1", 1),
+            ("9", 1),
+            ("10", 2),
+            ("99", 2),
+            ("100", 3),
+            ("9999", 4),
+            ("10000", 5),
+            ("007", 3),
+        ] {
+            let schematic = single_part(digits);
+            assert_eq!(schematic.parts[0].length, length);
+            assert_eq!(schematic.parts[0].value, digits.parse::<usize>().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_adjacency_1x1() {
+        let schematic = single_part("1");
+
+        // Around
+        assert!(touches(&schematic, 3, 3));
+        assert!(touches(&schematic, 3, 4));
+        assert!(touches(&schematic, 3, 5));
+        assert!(touches(&schematic, 4, 3));
+        assert!(touches(&schematic, 4, 5));
+        assert!(touches(&schematic, 5, 3));
+        assert!(touches(&schematic, 5, 4));
+        assert!(touches(&schematic, 5, 5));
+
+        // Above
+        assert!(!touches(&schematic, 2, 4));
+        // Below
+        assert!(!touches(&schematic, 6, 4));
+        // Left
+        assert!(!touches(&schematic, 4, 2));
+        // Right
+        assert!(!touches(&schematic, 4, 6));
+
+        // Top left
+        assert!(!touches(&schematic, 2, 2));
+        // Bottom left
+        assert!(!touches(&schematic, 6, 2));
+        // Top right
+        assert!(!touches(&schematic, 2, 6));
+        // Bottom right
+        assert!(!touches(&schematic, 6, 6));
+    }
+
+    #[test]
+    fn test_adjacency_2x1() {
+        let schematic = single_part("10");
+
+        // Corners
+        assert!(touches(&schematic, 3, 3));
+        assert!(touches(&schematic, 5, 3));
+        assert!(touches(&schematic, 5, 6));
+        assert!(touches(&schematic, 3, 6));
+
+        // Right
+        assert!(!touches(&schematic, 4, 7));
+        // Top right
+        assert!(!touches(&schematic, 2, 7));
+        // Bottom right
+        assert!(!touches(&schematic, 6, 7));
+    }
+
+    #[test]
+    fn test_adjacent_parts_are_unique() {
+        let schematic = Schematic::new("123\n.*.\n4.5");
+        assert_eq!(schematic.adjacent_parts(Coord::new(1, 1)), vec![0, 1, 2]);
+        assert_eq!(schematic.part_at(Coord::new(0, 2)), Some(0));
+        assert_eq!(schematic.part_at(Coord::new(1, 1)), None);
+    }
+
+    #[test]
+    fn test_connections() {
+        let schematic = Schematic::new("1.2.3\n.#.#.\n4....\n..*..\n....6");
+        let report = schematic.connections();
+
+        assert_eq!(report.keys().collect::<Vec<_>>(), vec![&'#', &'*']);
+        assert_eq!(report[&'#'][0].parts, vec![0, 1, 3]);
+        assert_eq!(report[&'#'][1].parts, vec![1, 2]);
+        assert_eq!(report[&'*'][0].parts, vec![]);
+
+        assert_eq!(schematic.ratio_sum('#', 3), 8);
+        assert_eq!(schematic.ratio_sum('#', 2), 6);
+        assert_eq!(schematic.ratio_sum('*', 2), 0);
+        assert_eq!(schematic.ratio_sum('$', 1), 0);
+
+        let lone = schematic.lone_parts();
+        assert_eq!(
+            lone.iter().map(|part| part.value).collect::<Vec<_>>(),
+            vec![6]
+        );
+    }
+
+    #[test]
+    fn test_parse_line_basics() {
+        assert_eq!(parse_line(5, ""), vec![]);
+        assert_eq!(
+            parse_line(5, "4"),
+            vec![Token {
+                sym: Symbol::Value(4),
+                pos: Coord::new(5, 0),
+                length: 1,
+            }]
+        );
+        assert_eq!(
+            parse_line(5, "*"),
+            vec![Token {
+                sym: Symbol::Mark('*'),
+                pos: Coord::new(5, 0),
+                length: 1,
+            }]
+        );
+        assert_eq!(
+            parse_line(5, "#"),
+            vec![Token {
+                sym: Symbol::Mark('#'),
+                pos: Coord::new(5, 0),
+                length: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_line_pseudoline1() {
+        assert_eq!(
+            parse_line(5, "*.42"),
+            vec![
+                Token {
+                    sym: Symbol::Mark('*'),
+                    pos: Coord::new(5, 0),
+                    length: 1,
+                },
+                Token {
+                    sym: Symbol::Value(42),
+                    pos: Coord::new(5, 2),
+                    length: 2,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_line_pseudoline2() {
+        assert_eq!(
+            parse_line(5, "*.42..069"),
+            vec![
+                Token {
+                    sym: Symbol::Mark('*'),
+                    pos: Coord::new(5, 0),
+                    length: 1,
+                },
+                Token {
+                    sym: Symbol::Value(42),
+                    pos: Coord::new(5, 2),
+                    length: 2,
+                },
+                Token {
+                    sym: Symbol::Value(69),
+                    pos: Coord::new(5, 6),
+                    length: 3,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_line_offsets() {
+        assert_eq!(
+            parse_line(5, ".*"),
+            vec![Token {
+                sym: Symbol::Mark('*'),
+                pos: Coord::new(5, 1),
+                length: 1,
+            }]
+        );
+        assert_eq!(parse_line(5, ".*."), parse_line(5, ".*"),);
+    }
+
+    #[test]
+    fn test_parse_line_non_ascii() {
+        assert_eq!(
+            parse_line(5, "é12"),
+            vec![
+                Token {
+                    sym: Symbol::Mark('é'),
+                    pos: Coord::new(5, 0),
+                    length: 1,
+                },
+                Token {
+                    sym: Symbol::Value(12),
+                    pos: Coord::new(5, 1),
+                    length: 2,
+                }
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use std::{collections::HashSet, fs};
+use day_04::{parse_cards, Card};
+use std::fs;
 
 fn main() {
     let input = fs::read_to_string("inputs/input.txt").unwrap();
@@ -6,38 +7,10 @@ fn main() {
 }
 
 fn calculate(input: String) -> String {
-    input
-        .lines()
-        .map(|line| {
-            if line.len() == 0 {
-                return 0;
-            }
-
-            let no_prefix = line
-                .chars()
-                .skip_while(|c| *c != ':')
-                .skip(2) // Skips the : and the following space
-                .collect::<String>();
-
-            let (win_chunk, mine_chunk) = no_prefix.split_once(" | ").unwrap();
-
-            let winners = win_chunk
-                .split_whitespace()
-                .map(|num| num.parse::<usize>().unwrap())
-                .collect::<HashSet<_>>();
-            let mine = mine_chunk
-                .split_whitespace()
-                .map(|num| num.parse::<usize>().unwrap())
-                .collect::<HashSet<_>>();
-
-            let matches = mine.intersection(&winners).count();
-
-            if matches == 0 {
-                0 as usize
-            } else {
-                usize::pow(2, (matches - 1) as u32)
-            }
-        })
+    parse_cards(&input)
+        .unwrap()
+        .iter()
+        .map(Card::points)
         .sum::<usize>()
         .to_string()
 }
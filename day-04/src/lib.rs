@@ -0,0 +1,164 @@
+use std::{collections::HashSet, ops::AddAssign, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: usize,
+    pub winners: HashSet<usize>,
+    pub mine: HashSet<usize>,
+}
+impl FromStr for Card {
+    type Err = CardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (prefix, numbers) = s.split_once(':').ok_or(CardParseError)?;
+        let id = prefix
+            .strip_prefix("Card")
+            .ok_or(CardParseError)?
+            .trim()
+            .parse()
+            .map_err(|_| CardParseError)?;
+
+        let (win_chunk, mine_chunk) = numbers.split_once(" | ").ok_or(CardParseError)?;
+        let parse_chunk = |chunk: &str| {
+            chunk
+                .split_whitespace()
+                .map(|num| num.parse::<usize>().map_err(|_| CardParseError))
+                .collect::<Result<HashSet<_>, _>>()
+        };
+
+        Ok(Card {
+            id,
+            winners: parse_chunk(win_chunk)?,
+            mine: parse_chunk(mine_chunk)?,
+        })
+    }
+}
+impl Card {
+    pub fn matches(&self) -> usize {
+        self.mine.intersection(&self.winners).count()
+    }
+
+    pub fn points(&self) -> usize {
+        match self.matches() {
+            0 => 0,
+            matches => usize::pow(2, (matches - 1) as u32),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CardParseError;
+
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, CardParseError> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(Card::from_str)
+        .collect()
+}
+
+/// How many instances of each card there are once all the copies are won.
+///
+/// Generic over the count type so adversarial inputs can use a big integer
+/// instead of overflowing `usize`.
+pub fn instances<T>(cards: &[Card]) -> Vec<T>
+where
+    T: Clone + From<u8> + for<'a> AddAssign<&'a T>,
+{
+    let mut counts = vec![T::from(1); cards.len()];
+
+    for (index, card) in cards.iter().enumerate() {
+        let (done, upcoming) = counts.split_at_mut(index + 1);
+        for copies in upcoming.iter_mut().take(card.matches()) {
+            *copies += &done[index];
+        }
+    }
+
+    counts
+}
+
+/// Why a card has the number of instances it has
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace<T> {
+    pub id: usize,
+    pub total: T,
+    /// Id of every earlier card that won copies of this one, with how many
+    /// copies it contributed. The original card is not listed.
+    pub contributors: Vec<(usize, T)>,
+}
+
+pub fn trace<T>(cards: &[Card], index: usize) -> Trace<T>
+where
+    T: Clone + From<u8> + for<'a> AddAssign<&'a T>,
+{
+    let counts = instances::<T>(&cards[..=index]);
+
+    let contributors = cards[..index]
+        .iter()
+        .zip(&counts)
+        .enumerate()
+        .filter(|(earlier, (card, _))| earlier + card.matches() >= index)
+        .map(|(_, (card, copies))| (card.id, copies.clone()))
+        .collect();
+
+    Trace {
+        id: cards[index].id,
+        total: counts[index].clone(),
+        contributors,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use num_bigint::BigUint;
+    use std::fs;
+
+    #[test]
+    fn test_parse() {
+        let card = Card::from_str("Card  12: 41 48 | 83 41  6").unwrap();
+        assert_eq!(card.id, 12);
+        assert_eq!(card.winners, HashSet::from([41, 48]));
+        assert_eq!(card.mine, HashSet::from([83, 41, 6]));
+        assert_eq!(card.matches(), 1);
+
+        assert!(Card::from_str("Crad 1: 1 | 2").is_err());
+        assert!(Card::from_str("Card 1: 1 2").is_err());
+    }
+
+    #[test]
+    fn test_instances() {
+        let input = fs::read_to_string("inputs/example1.txt").unwrap();
+        let cards = parse_cards(&input).unwrap();
+        assert_eq!(instances::<usize>(&cards), vec![1, 2, 4, 8, 14, 1]);
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = fs::read_to_string("inputs/example1.txt").unwrap();
+        let cards = parse_cards(&input).unwrap();
+        assert_eq!(
+            trace::<usize>(&cards, 4),
+            Trace {
+                id: 5,
+                total: 14,
+                contributors: vec![(1, 1), (3, 4), (4, 8)],
+            }
+        );
+    }
+
+    #[test]
+    fn test_big_counts() {
+        // Every card wins the next two, so counts grow like fibonacci numbers
+        let input = (1..=120)
+            .map(|id| format!("Card {}: 1 2 | 1 2", id))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let cards = parse_cards(&input).unwrap();
+
+        let big = instances::<BigUint>(&cards);
+        let wide = instances::<u128>(&cards);
+        assert!(wide[119] > u64::MAX as u128);
+        assert_eq!(big[119], BigUint::from(wide[119]));
+    }
+}
@@ -0,0 +1,340 @@
+use crate::{check::Issue, intersect_ranges, merge_ranges, Mapping, PiecewiseMap};
+use std::{
+    collections::{HashMap, VecDeque},
+    ops::Range,
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlmanacError {
+    /// Line number, counting from 1
+    Parse(usize),
+    UnknownCategory(String),
+    NoPath {
+        from: String,
+        to: String,
+    },
+    /// The mapping on `line` overlaps the one on `other`, so the conversion
+    /// depends on which one is picked
+    Ambiguous {
+        line: usize,
+        other: usize,
+    },
+    /// The seeds on this line can't be read as pairs of start and length,
+    /// there's one left over
+    UnpairedSeeds(usize),
+}
+
+/// One "x-to-y map:" block
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Layer {
+    pub from: String,
+    pub to: String,
+    /// Line number of the header, counting from 1
+    pub line: usize,
+    /// As written, with the line number each came from
+    pub mappings: Vec<(usize, Mapping)>,
+}
+impl Layer {
+    pub fn map(&self) -> PiecewiseMap {
+        PiecewiseMap::new(
+            self.mappings
+                .iter()
+                .map(|(_, mapping)| mapping.clone())
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Almanac {
+    pub seeds: Vec<isize>,
+    /// Line number of the seeds, counting from 1
+    pub seeds_line: usize,
+    pub layers: Vec<Layer>,
+}
+impl FromStr for Almanac {
+    type Err = AlmanacError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut almanac = Almanac::default();
+
+        for (index, line) in s.lines().enumerate() {
+            let line_number = index + 1;
+            let error = AlmanacError::Parse(line_number);
+
+            if line.is_empty() {
+                continue;
+            } else if let Some(seeds) = line.strip_prefix("seeds:") {
+                almanac.seeds = seeds
+                    .split_whitespace()
+                    .map(|num| num.parse::<isize>())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| error)?;
+                almanac.seeds_line = line_number;
+            } else if let Some(header) = line.strip_suffix(" map:") {
+                let (from, to) = header.split_once("-to-").ok_or(error)?;
+                almanac.layers.push(Layer {
+                    from: from.to_owned(),
+                    to: to.to_owned(),
+                    line: line_number,
+                    mappings: vec![],
+                });
+            } else {
+                let mapping = Mapping::from_str(line).map_err(|_| error.clone())?;
+                almanac
+                    .layers
+                    .last_mut()
+                    .ok_or(error)?
+                    .mappings
+                    .push((line_number, mapping));
+            }
+        }
+
+        Ok(almanac)
+    }
+}
+impl Almanac {
+    /// Each seed on its own
+    pub fn seeds_as_units(&self) -> Vec<Range<isize>> {
+        self.seeds.iter().map(|&seed| seed..(seed + 1)).collect()
+    }
+
+    /// Seeds read as pairs of start and length
+    pub fn seeds_as_ranges(&self) -> Result<Vec<Range<isize>>, AlmanacError> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(AlmanacError::UnpairedSeeds(self.seeds_line));
+        }
+
+        Ok(self
+            .seeds
+            .chunks_exact(2)
+            .map(|elems| {
+                let (base, size) = (elems[0], elems[1]);
+                base..(base + size)
+            })
+            .collect())
+    }
+
+    pub fn categories(&self) -> Vec<&str> {
+        let mut categories = vec![];
+        for layer in &self.layers {
+            for category in [layer.from.as_str(), layer.to.as_str()] {
+                if !categories.contains(&category) {
+                    categories.push(category);
+                }
+            }
+        }
+        categories
+    }
+
+    /// Indices of the layers leading from one category to another
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<usize>, AlmanacError> {
+        for category in [from, to] {
+            if !self.categories().contains(&category) {
+                return Err(AlmanacError::UnknownCategory(category.to_owned()));
+            }
+        }
+
+        // Breadth first, remembering the layer used to reach each category
+        let mut reached: HashMap<&str, Option<usize>> = HashMap::from([(from, None)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(category) = queue.pop_front() {
+            for (index, layer) in self.layers.iter().enumerate() {
+                if layer.from == category && !reached.contains_key(layer.to.as_str()) {
+                    reached.insert(&layer.to, Some(index));
+                    queue.push_back(&layer.to);
+                }
+            }
+        }
+
+        let mut path = vec![];
+        let mut category = to;
+        loop {
+            match reached.get(category) {
+                None => {
+                    return Err(AlmanacError::NoPath {
+                        from: from.to_owned(),
+                        to: to.to_owned(),
+                    })
+                }
+                Some(None) => break,
+                Some(Some(index)) => {
+                    path.push(*index);
+                    category = &self.layers[*index].from;
+                }
+            }
+        }
+
+        path.reverse();
+        Ok(path)
+    }
+
+    /// All of the layers between two categories folded into one map.
+    /// Refuses to guess if any of the layers on the way are ambiguous.
+    pub fn conversion(&self, from: &str, to: &str) -> Result<PiecewiseMap, AlmanacError> {
+        let path = self.path(from, to)?;
+
+        for index in &path {
+            for issue in self.layers[*index].check() {
+                if let Issue::Overlap { line, other } = issue {
+                    return Err(AlmanacError::Ambiguous { line, other });
+                }
+            }
+        }
+
+        Ok(path
+            .into_iter()
+            .fold(PiecewiseMap::default(), |acc, index| {
+                acc.compose(&self.layers[index].map())
+            }))
+    }
+
+    /// Lowest location any of the seeds end up in
+    pub fn lowest_location(&self, seeds: &[Range<isize>]) -> Result<Option<isize>, AlmanacError> {
+        let location = self.conversion("seed", "location")?;
+
+        Ok(seeds
+            .iter()
+            .filter_map(|seed_group| location.min_over(seed_group.clone()))
+            .min())
+    }
+
+    /// Which of the seeds end up in `locations`
+    pub fn seeds_landing_in(
+        &self,
+        seeds: &[Range<isize>],
+        locations: Range<isize>,
+    ) -> Result<Vec<Range<isize>>, AlmanacError> {
+        Ok(intersect_ranges(
+            &merge_ranges(seeds.to_vec()),
+            &self.conversion("seed", "location")?.preimage(locations),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_parsing() {
+        let input = fs::read_to_string("inputs/example.txt").unwrap();
+        let almanac = Almanac::from_str(&input).unwrap();
+
+        assert_eq!(almanac.seeds, vec![79, 14, 55, 13]);
+        assert_eq!(almanac.layers.len(), 7);
+        assert_eq!(almanac.layers[1].from, "soil");
+        assert_eq!(almanac.layers[1].to, "fertilizer");
+        assert_eq!(almanac.layers[1].line, 7);
+        assert_eq!(almanac.layers[1].mappings[0].0, 8);
+
+        assert_eq!(
+            Almanac::from_str("seeds: 1 2\n\n1 2 3"),
+            Err(AlmanacError::Parse(3))
+        );
+        assert_eq!(
+            Almanac::from_str("seeds: 1 2\nsoil map:"),
+            Err(AlmanacError::Parse(2))
+        );
+    }
+
+    #[test]
+    fn test_seed_parsing() {
+        let input = fs::read_to_string("inputs/example.txt").unwrap();
+        let almanac = Almanac::from_str(&input).unwrap();
+
+        assert_eq!(almanac.seeds_as_ranges(), Ok(vec![79..93, 55..68]));
+        assert_eq!(almanac.seeds_as_units()[1], 14..15);
+
+        let odd = Almanac::from_str("\nseeds: 79 14 55").unwrap();
+        assert_eq!(odd.seeds_as_units().len(), 3);
+        assert_eq!(odd.seeds_as_ranges(), Err(AlmanacError::UnpairedSeeds(2)));
+    }
+
+    #[test]
+    fn test_paths() {
+        let input = fs::read_to_string("inputs/example.txt").unwrap();
+        let almanac = Almanac::from_str(&input).unwrap();
+
+        assert_eq!(almanac.path("seed", "location"), Ok((0..7).collect()));
+        assert_eq!(almanac.path("water", "temperature"), Ok(vec![3, 4]));
+        assert_eq!(almanac.path("soil", "soil"), Ok(vec![]));
+        assert_eq!(
+            almanac.path("location", "seed"),
+            Err(AlmanacError::NoPath {
+                from: "location".into(),
+                to: "seed".into()
+            })
+        );
+        assert_eq!(
+            almanac.path("seed", "sunlight"),
+            Err(AlmanacError::UnknownCategory("sunlight".into()))
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        let input = fs::read_to_string("inputs/example.txt").unwrap();
+        let almanac = Almanac::from_str(&input).unwrap();
+
+        // Worked through in the puzzle text
+        let soil = almanac.conversion("seed", "soil").unwrap();
+        assert_eq!(soil.apply(79), 81);
+        let location = almanac.conversion("seed", "location").unwrap();
+        assert_eq!(location.apply(79), 82);
+        assert_eq!(location.apply(14), 43);
+        assert_eq!(location.apply(55), 86);
+        assert_eq!(location.apply(13), 35);
+
+        let light = almanac.conversion("fertilizer", "light").unwrap();
+        assert_eq!(light.apply(81), 74);
+    }
+
+    #[test]
+    fn test_walking_backwards() {
+        let input = fs::read_to_string("inputs/example.txt").unwrap();
+        let almanac = Almanac::from_str(&input).unwrap();
+        let seeds = almanac.seeds_as_ranges().unwrap();
+
+        // Nothing lands below the answer, and the answer itself is hit
+        let answer = 46..47;
+        assert!(almanac.seeds_landing_in(&seeds, 0..46).unwrap().is_empty());
+        assert_eq!(
+            almanac.seeds_landing_in(&seeds, answer.clone()).unwrap(),
+            vec![82..83]
+        );
+
+        // Layer by layer gives the same result as the composed map
+        let by_layers = almanac
+            .layers
+            .iter()
+            .rev()
+            .fold(vec![answer], |targets, layer| {
+                layer.map().preimage_all(&targets)
+            });
+        assert!(by_layers.contains(&(82..83)));
+    }
+
+    #[test]
+    fn test_for_overlaping_ranges() {
+        let input = fs::read_to_string("inputs/input.txt").unwrap();
+        let almanac = Almanac::from_str(&input).unwrap();
+
+        almanac.layers.into_iter().for_each(|layer| {
+            let mut ranges: Vec<_> = layer
+                .mappings
+                .into_iter()
+                .map(|(_, mapping)| mapping.range)
+                .collect();
+            ranges.sort_by_key(|range| range.start);
+
+            let mut end = -1;
+            for range in &ranges {
+                assert!(!range.contains(&end));
+                end = range.end - 1; // Gives the non-inclusive end
+            }
+        });
+    }
+}
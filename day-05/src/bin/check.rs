@@ -0,0 +1,27 @@
+use day_05::almanac::Almanac;
+use std::{env, fs, process, str::FromStr};
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "inputs/input.txt".to_owned());
+    let input = fs::read_to_string(&path).unwrap();
+
+    let almanac = match Almanac::from_str(&input) {
+        Ok(almanac) => almanac,
+        Err(err) => {
+            println!("{}: can't parse: {:?}", path, err);
+            process::exit(1);
+        }
+    };
+
+    let issues = almanac.check();
+    for issue in &issues {
+        println!("{}: {}", path, issue);
+    }
+
+    if issues.iter().any(|issue| issue.is_ambiguous()) {
+        println!("{}: ambiguous, the solvers will refuse it", path);
+        process::exit(1);
+    }
+}
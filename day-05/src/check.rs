@@ -0,0 +1,184 @@
+use crate::almanac::{Almanac, Layer};
+use std::{fmt, ops::Range};
+
+/// Something suspicious in an almanac. Line numbers count from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// The mapping on `line` covers sources already covered on `other`
+    Overlap { line: usize, other: usize },
+    /// Sources left unmapped between the mapping on `other` and the one on
+    /// `line`, which starts after it
+    Gap {
+        line: usize,
+        other: usize,
+        range: Range<isize>,
+    },
+    /// A mapping that doesn't move anything
+    Identity { line: usize },
+    /// A mapping with a length of zero
+    Empty { line: usize },
+    /// A mapping with a length below zero
+    Negative { line: usize },
+    /// A category that no chain of layers starting from seeds leads to
+    Unreachable { line: usize, category: String },
+}
+impl Issue {
+    /// Overlaps make the almanac ambiguous, the rest are just odd
+    pub fn is_ambiguous(&self) -> bool {
+        matches!(self, Issue::Overlap { .. })
+    }
+}
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::Overlap { line, other } => {
+                write!(f, "line {}: overlaps the mapping on line {}", line, other)
+            }
+            Issue::Gap { line, other, range } => write!(
+                f,
+                "line {}: {}..{} is not mapped between this and the mapping on line {}",
+                line, range.start, range.end, other
+            ),
+            Issue::Identity { line } => write!(f, "line {}: maps everything to itself", line),
+            Issue::Empty { line } => write!(f, "line {}: has a length of zero", line),
+            Issue::Negative { line } => write!(f, "line {}: has a negative length", line),
+            Issue::Unreachable { line, category } => {
+                write!(f, "line {}: {} can't be reached from seed", line, category)
+            }
+        }
+    }
+}
+
+impl Layer {
+    pub fn check(&self) -> Vec<Issue> {
+        let mut issues = vec![];
+
+        let mut mappings = vec![];
+        for (line, mapping) in &self.mappings {
+            if mapping.range.end < mapping.range.start {
+                issues.push(Issue::Negative { line: *line });
+                continue;
+            }
+            if mapping.range.is_empty() {
+                issues.push(Issue::Empty { line: *line });
+                continue;
+            }
+            if mapping.shift == 0 {
+                issues.push(Issue::Identity { line: *line });
+            }
+            mappings.push((*line, mapping));
+        }
+        mappings.sort_by_key(|(line, mapping)| (mapping.range.start, *line));
+
+        // Sweep keeping track of the mapping that reaches the furthest
+        let mut furthest: Option<(usize, isize)> = None;
+        for (line, mapping) in mappings {
+            match furthest {
+                Some((other, end)) if mapping.range.start < end => {
+                    issues.push(Issue::Overlap { line, other });
+                }
+                Some((other, end)) if mapping.range.start > end => {
+                    issues.push(Issue::Gap {
+                        line,
+                        other,
+                        range: end..mapping.range.start,
+                    });
+                }
+                _ => {}
+            }
+
+            if furthest.is_none_or(|(_, end)| mapping.range.end > end) {
+                furthest = Some((line, mapping.range.end));
+            }
+        }
+
+        issues
+    }
+}
+
+impl Almanac {
+    pub fn check(&self) -> Vec<Issue> {
+        let mut issues: Vec<Issue> = self.layers.iter().flat_map(Layer::check).collect();
+
+        for layer in &self.layers {
+            if self.path("seed", &layer.from).is_err() {
+                issues.push(Issue::Unreachable {
+                    line: layer.line,
+                    category: layer.from.clone(),
+                });
+            }
+        }
+
+        issues.sort_by_key(|issue| match issue {
+            Issue::Overlap { line, .. }
+            | Issue::Gap { line, .. }
+            | Issue::Identity { line }
+            | Issue::Empty { line }
+            | Issue::Negative { line }
+            | Issue::Unreachable { line, .. } => *line,
+        });
+        issues
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::almanac::AlmanacError;
+    use std::{fs, str::FromStr};
+
+    #[test]
+    fn test_clean_inputs() {
+        for file in ["inputs/example.txt", "inputs/input.txt"] {
+            let almanac = Almanac::from_str(&fs::read_to_string(file).unwrap()).unwrap();
+            assert!(!almanac.check().iter().any(Issue::is_ambiguous));
+        }
+    }
+
+    #[test]
+    fn test_issues() {
+        let input = [
+            "seeds: 1 2",
+            "",
+            "seed-to-soil map:",
+            "0 10 10",
+            "5 15 10",
+            "40 40 5",
+            "1 2 0",
+            "1 2 -3",
+            "",
+            "water-to-light map:",
+            "0 30 2",
+        ]
+        .join("\n");
+        let almanac = Almanac::from_str(&input).unwrap();
+
+        assert_eq!(
+            almanac.check(),
+            vec![
+                Issue::Overlap { line: 5, other: 4 },
+                Issue::Identity { line: 6 },
+                Issue::Gap {
+                    line: 6,
+                    other: 5,
+                    range: 25..40
+                },
+                Issue::Empty { line: 7 },
+                Issue::Negative { line: 8 },
+                Issue::Unreachable {
+                    line: 10,
+                    category: "water".into()
+                },
+            ]
+        );
+        assert_eq!(
+            almanac.check()[2].to_string(),
+            "line 6: 25..40 is not mapped between this and the mapping on line 5"
+        );
+
+        assert_eq!(
+            almanac.conversion("seed", "soil"),
+            Err(AlmanacError::Ambiguous { line: 5, other: 4 })
+        );
+    }
+}
@@ -0,0 +1,350 @@
+use std::{ops::Range, str::FromStr};
+
+pub mod almanac;
+pub mod check;
+
+/// Moves every value in `range` by `shift`
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Mapping {
+    pub range: Range<isize>,
+    pub shift: isize,
+}
+impl FromStr for Mapping {
+    type Err = MappingParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let nums = s
+            .split_whitespace()
+            .map(|num| num.parse::<isize>().map_err(|_| MappingParseError))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let [to, from, length] = nums[..] else {
+            return Err(MappingParseError);
+        };
+
+        Ok(Self {
+            range: from..(from + length),
+            shift: to - from,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct MappingParseError;
+
+/// A function over the integers that is a shift on each of a sorted set of
+/// disjoint ranges and the identity everywhere else.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct PiecewiseMap {
+    /// Sorted, non-overlapping and without identity segments
+    pub mappings: Vec<Mapping>,
+}
+impl PiecewiseMap {
+    /// Expects the ranges not to overlap
+    pub fn new(mappings: Vec<Mapping>) -> Self {
+        let mut mappings: Vec<Mapping> = mappings
+            .into_iter()
+            .filter(|mapping| mapping.shift != 0 && !mapping.range.is_empty())
+            .collect();
+        mappings.sort_by_key(|mapping| mapping.range.start);
+
+        // Neighbours that shift by the same amount are one segment
+        let mappings = mappings
+            .into_iter()
+            .fold(vec![], |mut acc: Vec<Mapping>, next| {
+                match acc.last_mut() {
+                    Some(last)
+                        if last.range.end == next.range.start && last.shift == next.shift =>
+                    {
+                        last.range.end = next.range.end;
+                    }
+                    _ => acc.push(next),
+                }
+                acc
+            });
+
+        Self { mappings }
+    }
+
+    pub fn apply(&self, value: isize) -> isize {
+        let index = self
+            .mappings
+            .partition_point(|mapping| mapping.range.end <= value);
+
+        match self.mappings.get(index) {
+            Some(mapping) if mapping.range.contains(&value) => value + mapping.shift,
+            _ => value,
+        }
+    }
+
+    /// Splits `range` into consecutive pieces, each moved by a single shift.
+    /// The identity stretches between segments come out with a shift of 0.
+    pub fn pieces(&self, range: Range<isize>) -> Vec<Mapping> {
+        let mut out = vec![];
+        let mut marker = range.start;
+
+        let first = self
+            .mappings
+            .partition_point(|mapping| mapping.range.end <= range.start);
+        for mapping in &self.mappings[first..] {
+            if marker >= range.end || mapping.range.start >= range.end {
+                break;
+            }
+
+            if mapping.range.start > marker {
+                out.push(Mapping {
+                    range: marker..mapping.range.start,
+                    shift: 0,
+                });
+                marker = mapping.range.start;
+            }
+
+            let end = mapping.range.end.min(range.end);
+            out.push(Mapping {
+                range: marker..end,
+                shift: mapping.shift,
+            });
+            marker = end;
+        }
+
+        if marker < range.end {
+            out.push(Mapping {
+                range: marker..range.end,
+                shift: 0,
+            });
+        }
+
+        out
+    }
+
+    /// The map that applies `self` first and then `other`
+    pub fn compose(&self, other: &PiecewiseMap) -> PiecewiseMap {
+        let mappings = self
+            .pieces(isize::MIN..isize::MAX)
+            .into_iter()
+            .flat_map(|first| {
+                let image = (first.range.start + first.shift)..(first.range.end + first.shift);
+                other.pieces(image).into_iter().map(move |second| Mapping {
+                    range: (second.range.start - first.shift)..(second.range.end - first.shift),
+                    shift: first.shift + second.shift,
+                })
+            })
+            .collect();
+
+        PiecewiseMap::new(mappings)
+    }
+
+    /// Everything `range` maps to, as sorted disjoint ranges
+    pub fn image(&self, range: Range<isize>) -> Vec<Range<isize>> {
+        merge_ranges(
+            self.pieces(range)
+                .into_iter()
+                .map(|piece| (piece.range.start + piece.shift)..(piece.range.end + piece.shift))
+                .collect(),
+        )
+    }
+
+    /// Smallest value `range` maps to
+    pub fn min_over(&self, range: Range<isize>) -> Option<isize> {
+        self.pieces(range)
+            .into_iter()
+            .map(|piece| piece.range.start + piece.shift)
+            .min()
+    }
+
+    /// Every value that maps into `target`, as sorted disjoint ranges
+    pub fn preimage(&self, target: Range<isize>) -> Vec<Range<isize>> {
+        // Inside target but outside every segment, so left where it is
+        let unmoved = self
+            .pieces(target.clone())
+            .into_iter()
+            .filter(|piece| piece.shift == 0)
+            .map(|piece| piece.range);
+
+        let moved = self.mappings.iter().filter_map(|mapping| {
+            let start = (mapping.range.start + mapping.shift).max(target.start);
+            let end = (mapping.range.end + mapping.shift).min(target.end);
+            (start < end).then(|| (start - mapping.shift)..(end - mapping.shift))
+        });
+
+        merge_ranges(unmoved.chain(moved).collect())
+    }
+
+    /// Preimage of a set of ranges, such as one produced by a later layer
+    pub fn preimage_all(&self, targets: &[Range<isize>]) -> Vec<Range<isize>> {
+        merge_ranges(
+            targets
+                .iter()
+                .flat_map(|target| self.preimage(target.clone()))
+                .collect(),
+        )
+    }
+
+    /// How many values of `range` land inside `target`
+    pub fn count_into(&self, range: Range<isize>, target: Range<isize>) -> usize {
+        self.pieces(range)
+            .into_iter()
+            .map(|piece| {
+                let start = (piece.range.start + piece.shift).max(target.start);
+                let end = (piece.range.end + piece.shift).min(target.end);
+                (end - start).max(0) as usize
+            })
+            .sum()
+    }
+}
+
+/// Sorts ranges and joins the ones that overlap or touch
+pub fn merge_ranges(mut input: Vec<Range<isize>>) -> Vec<Range<isize>> {
+    input.retain(|range| !range.is_empty());
+    input.sort_by_key(|range| range.start);
+    let mut coll: Vec<Range<isize>> = vec![];
+
+    for i in input.into_iter() {
+        match coll.last_mut() {
+            Some(last) if last.end >= i.start => last.end = last.end.max(i.end),
+            _ => coll.push(i),
+        }
+    }
+
+    coll
+}
+
+/// Parts covered by both sets of sorted disjoint ranges
+pub fn intersect_ranges(a: &[Range<isize>], b: &[Range<isize>]) -> Vec<Range<isize>> {
+    let (mut i, mut j) = (0, 0);
+    let mut out = vec![];
+
+    while i < a.len() && j < b.len() {
+        let start = a[i].start.max(b[j].start);
+        let end = a[i].end.min(b[j].end);
+        if start < end {
+            out.push(start..end);
+        }
+
+        if a[i].end < b[j].end {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn map(segments: &[(Range<isize>, isize)]) -> PiecewiseMap {
+        PiecewiseMap::new(
+            segments
+                .iter()
+                .map(|(range, shift)| Mapping {
+                    range: range.clone(),
+                    shift: *shift,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_apply() {
+        // seed-to-soil from the example
+        let layer = map(&[(98..100, -48), (50..98, 2)]);
+
+        assert_eq!(layer.apply(0), 0);
+        assert_eq!(layer.apply(49), 49);
+        assert_eq!(layer.apply(50), 52);
+        assert_eq!(layer.apply(97), 99);
+        assert_eq!(layer.apply(98), 50);
+        assert_eq!(layer.apply(99), 51);
+        assert_eq!(layer.apply(100), 100);
+    }
+
+    #[test]
+    fn test_pieces() {
+        let layer = map(&[(10..20, 5), (30..40, -5)]);
+        assert_eq!(
+            layer
+                .pieces(0..35)
+                .into_iter()
+                .map(|piece| (piece.range, piece.shift))
+                .collect::<Vec<_>>(),
+            vec![(0..10, 0), (10..20, 5), (20..30, 0), (30..35, -5)]
+        );
+        assert_eq!(layer.pieces(12..15).len(), 1);
+        assert!(layer.pieces(5..5).is_empty());
+    }
+
+    #[test]
+    fn test_compose_matches_walking_layers() {
+        let first = map(&[(10..20, 5), (30..40, -5)]);
+        let second = map(&[(0..12, 100), (22..28, -20)]);
+        let both = first.compose(&second);
+
+        for value in -5..50 {
+            assert_eq!(both.apply(value), second.apply(first.apply(value)));
+        }
+    }
+
+    #[test]
+    fn test_compose_identity() {
+        let layer = map(&[(10..20, 5)]);
+        assert_eq!(layer.compose(&PiecewiseMap::default()), layer);
+        assert_eq!(PiecewiseMap::default().compose(&layer), layer);
+
+        let back = map(&[(15..25, -5)]);
+        assert_eq!(layer.compose(&back), map(&[(20..25, -5)]));
+    }
+
+    #[test]
+    fn test_queries() {
+        let layer = map(&[(10..20, 5), (30..40, -25)]);
+
+        assert_eq!(layer.image(0..35), vec![0..10, 15..30]);
+        assert_eq!(layer.min_over(12..35), Some(5));
+        assert_eq!(layer.min_over(3..3), None);
+        assert_eq!(layer.count_into(0..40, 5..16), 5 + 1 + 10);
+    }
+
+    #[test]
+    fn test_preimage() {
+        let layer = map(&[(10..20, 5), (30..40, -25)]);
+
+        // 5..10 stays, 10..15 is moved away, 15..16 comes from 10..11
+        // and 5..15 from 30..40
+        assert_eq!(layer.preimage(5..16), vec![5..11, 30..40]);
+        assert_eq!(layer.preimage(20..25), vec![15..25]);
+
+        for value in 0..50 {
+            let forward = layer.apply(value);
+            assert!(layer
+                .preimage(forward..forward + 1)
+                .iter()
+                .any(|range| range.contains(&value)));
+        }
+    }
+
+    #[test]
+    fn test_preimage_of_composition() {
+        let first = map(&[(10..20, 5), (30..40, -5)]);
+        let second = map(&[(0..12, 100), (22..28, -20)]);
+        let both = first.compose(&second);
+
+        for target in [0..10, 100..105, 2..8, 20..30] {
+            assert_eq!(
+                both.preimage(target.clone()),
+                first.preimage_all(&second.preimage(target))
+            );
+        }
+    }
+
+    #[test]
+    fn test_intersect_ranges() {
+        assert_eq!(
+            intersect_ranges(&[0..10, 20..30], &[5..25, 28..40]),
+            vec![5..10, 20..25, 28..30]
+        );
+        assert!(intersect_ranges(&[0..10, 20..30], &[]).is_empty());
+    }
+}
@@ -0,0 +1,275 @@
+use num_bigint::BigUint;
+use std::{ops::RangeInclusive, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Race {
+    pub time: BigUint,
+    pub distance: BigUint,
+}
+impl Race {
+    pub fn new(time: impl Into<BigUint>, distance: impl Into<BigUint>) -> Self {
+        Race {
+            time: time.into(),
+            distance: distance.into(),
+        }
+    }
+
+    fn beats_record(&self, hold: &BigUint) -> bool {
+        hold <= &self.time && hold * (&self.time - hold) > self.distance
+    }
+
+    /// Shortest and longest hold times that beat the record, if any do.
+    ///
+    /// Holding for `h` travels `h * (time - h)`, so the winning holds lie
+    /// strictly between the roots of `h^2 - time * h + distance`. The roots
+    /// are found with an integer square root and then nudged onto the first
+    /// whole hold that actually wins.
+    pub fn winning_holds(&self) -> Option<RangeInclusive<BigUint>> {
+        // The best hold is half of the time, if even that loses nothing wins
+        if !self.beats_record(&(&self.time / 2u32)) {
+            return None;
+        }
+
+        let squared = &self.time * &self.time;
+        let root = (squared - &self.distance * 4u32).sqrt();
+        let mut first = (&self.time - root) / 2u32;
+
+        while !self.beats_record(&first) {
+            first += 1u32;
+        }
+        while first > BigUint::ZERO && self.beats_record(&(&first - 1u32)) {
+            first -= 1u32;
+        }
+
+        // The curve is symmetric around time / 2
+        let last = &self.time - &first;
+        Some(first..=last)
+    }
+
+    pub fn ways_to_win(&self) -> BigUint {
+        match self.winning_holds() {
+            Some(holds) => holds.end() - holds.start() + 1u32,
+            None => BigUint::ZERO,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SheetParseError {
+    MissingRow(&'static str),
+    DuplicateRow(&'static str),
+    /// Line number, counting from 1
+    UnknownRow(usize),
+    BadNumber(String),
+    ColumnMismatch {
+        times: usize,
+        distances: usize,
+    },
+    /// Both rows are there but have no numbers on them
+    NoRaces,
+}
+
+/// The "Time:" and "Distance:" rows of a race sheet, column by column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceSheet {
+    pub times: Vec<BigUint>,
+    pub distances: Vec<BigUint>,
+    /// Both rows as written, leading zeros and all, for kerning
+    raw_times: Vec<String>,
+    raw_distances: Vec<String>,
+}
+impl FromStr for RaceSheet {
+    type Err = SheetParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut times = None;
+        let mut distances = None;
+
+        for (index, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (label, row) = line
+                .split_once(':')
+                .ok_or(SheetParseError::UnknownRow(index + 1))?;
+            let (label, slot) = match label.trim() {
+                "Time" => ("Time", &mut times),
+                "Distance" => ("Distance", &mut distances),
+                _ => return Err(SheetParseError::UnknownRow(index + 1)),
+            };
+            if slot.is_some() {
+                return Err(SheetParseError::DuplicateRow(label));
+            }
+
+            *slot = Some(
+                row.split_whitespace()
+                    .map(|val| {
+                        // Plain digits only, parse would also take a sign or
+                        // underscores and those don't survive kerning
+                        if !val.bytes().all(|b| b.is_ascii_digit()) {
+                            return Err(SheetParseError::BadNumber(val.to_owned()));
+                        }
+                        let num = val
+                            .parse::<BigUint>()
+                            .map_err(|_| SheetParseError::BadNumber(val.to_owned()))?;
+                        Ok((num, val.to_owned()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+        }
+
+        let (times, raw_times): (Vec<BigUint>, Vec<String>) = times
+            .ok_or(SheetParseError::MissingRow("Time"))?
+            .into_iter()
+            .unzip();
+        let (distances, raw_distances): (Vec<BigUint>, Vec<String>) = distances
+            .ok_or(SheetParseError::MissingRow("Distance"))?
+            .into_iter()
+            .unzip();
+        if times.len() != distances.len() {
+            return Err(SheetParseError::ColumnMismatch {
+                times: times.len(),
+                distances: distances.len(),
+            });
+        }
+        if times.is_empty() {
+            return Err(SheetParseError::NoRaces);
+        }
+
+        Ok(RaceSheet {
+            times,
+            distances,
+            raw_times,
+            raw_distances,
+        })
+    }
+}
+impl RaceSheet {
+    /// One race per column
+    pub fn races(&self) -> Vec<Race> {
+        self.times
+            .iter()
+            .zip(&self.distances)
+            .map(|(time, distance)| Race::new(time.clone(), distance.clone()))
+            .collect()
+    }
+
+    /// The single race you get by ignoring the spaces between the columns.
+    /// Joins the digits as written, so a column like `05` keeps its zero.
+    pub fn kerned_race(&self) -> Race {
+        // Every token is plain digits and there's at least one of them
+        let join = |row: &[String]| row.concat().parse::<BigUint>().unwrap();
+
+        Race::new(join(&self.raw_times), join(&self.raw_distances))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_example_races() {
+        assert_eq!(
+            Race::new(7u32, 9u32).winning_holds(),
+            Some(2u32.into()..=5u32.into())
+        );
+        assert_eq!(Race::new(15u32, 40u32).ways_to_win(), 8u32.into());
+        assert_eq!(
+            Race::new(30u32, 200u32).winning_holds(),
+            Some(11u32.into()..=19u32.into())
+        );
+        assert_eq!(
+            Race::new(71530u32, 940200u32).ways_to_win(),
+            71503u32.into()
+        );
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        for time in 0u32..60 {
+            for distance in 0u32..(time * time / 4 + 3) {
+                let expected = (0..=time).filter(|h| h * (time - h) > distance).count();
+                assert_eq!(
+                    Race::new(time, distance).ways_to_win(),
+                    BigUint::from(expected),
+                    "time {} distance {}",
+                    time,
+                    distance
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_sheet_parsing() {
+        let sheet = RaceSheet::from_str("Distance:  9  40  200\nTime:      7  15   30\n").unwrap();
+
+        assert_eq!(
+            sheet.races(),
+            vec![
+                Race::new(7u32, 9u32),
+                Race::new(15u32, 40u32),
+                Race::new(30u32, 200u32)
+            ]
+        );
+        assert_eq!(sheet.kerned_race(), Race::new(71530u32, 940200u32));
+
+        let zeros = RaceSheet::from_str("Time: 7 05\nDistance: 9 01").unwrap();
+        assert_eq!(zeros.times, vec![7u32.into(), 5u32.into()]);
+        assert_eq!(zeros.kerned_race(), Race::new(705u32, 901u32));
+    }
+
+    #[test]
+    fn test_sheet_errors() {
+        assert_eq!(
+            RaceSheet::from_str("Time: 1 2\nDistance: 3"),
+            Err(SheetParseError::ColumnMismatch {
+                times: 2,
+                distances: 1
+            })
+        );
+        assert_eq!(
+            RaceSheet::from_str("Time: 1"),
+            Err(SheetParseError::MissingRow("Distance"))
+        );
+        assert_eq!(
+            RaceSheet::from_str("Time: 1\nTime: 2"),
+            Err(SheetParseError::DuplicateRow("Time"))
+        );
+        assert_eq!(
+            RaceSheet::from_str("Time: 1\nSpeed: 2"),
+            Err(SheetParseError::UnknownRow(2))
+        );
+        assert_eq!(
+            RaceSheet::from_str("Time: 1\nDistance: x"),
+            Err(SheetParseError::BadNumber("x".into()))
+        );
+        assert_eq!(
+            RaceSheet::from_str("Time: 7 +5\nDistance: 9 1"),
+            Err(SheetParseError::BadNumber("+5".into()))
+        );
+        assert_eq!(
+            RaceSheet::from_str("Time: 7 1_5\nDistance: 9 1"),
+            Err(SheetParseError::BadNumber("1_5".into()))
+        );
+        assert_eq!(
+            RaceSheet::from_str("Time:\nDistance:"),
+            Err(SheetParseError::NoRaces)
+        );
+    }
+
+    #[test]
+    fn test_huge_race() {
+        let time: BigUint = "123456789012345678901234567890".parse().unwrap();
+        let distance: BigUint = "98765432109876543210987654321".parse().unwrap();
+        let race = Race::new(time.clone(), distance.clone());
+
+        let holds = race.winning_holds().unwrap();
+        let (first, last) = (holds.start().clone(), holds.end().clone());
+        assert!(&first * (&time - &first) > distance);
+        assert!((&first - 1u32) * (&time - &first + 1u32) <= distance);
+        assert_eq!(&first + &last, time);
+    }
+}
@@ -0,0 +1,25 @@
+use day_07::{
+    report::{report, to_csv, to_table},
+    Ruleset,
+};
+use std::{env, fs};
+
+const USAGE: &str = "usage: report <1 | 2> [table | csv]";
+
+fn main() {
+    let input = fs::read_to_string("inputs/input.txt").unwrap();
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let ruleset = match args.first().map(String::as_str) {
+        Some("1") => Ruleset::standard(),
+        Some("2") => Ruleset::jokers(),
+        _ => return println!("{}", USAGE),
+    };
+    let rows = report(&ruleset, ruleset.parse_hands(&input).unwrap());
+
+    match args.get(1).map(String::as_str) {
+        None | Some("table") => println!("{}", to_table(&rows)),
+        Some("csv") => println!("{}", to_csv(&rows)),
+        _ => println!("{}", USAGE),
+    }
+}
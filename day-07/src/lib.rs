@@ -0,0 +1,311 @@
+use std::cmp::Ordering;
+
+pub mod report;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct ParseCardError;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Category {
+    pub name: String,
+    /// Smallest group sizes, largest first, a hand needs to count as this.
+    /// A full house is `[3, 2]`, a pair is `[2]`.
+    pub pattern: Vec<usize>,
+}
+impl Category {
+    pub fn new(name: &str, pattern: &[usize]) -> Self {
+        Category {
+            name: name.to_owned(),
+            pattern: pattern.to_vec(),
+        }
+    }
+
+    /// Whether a sorted count profile is at least as good as the pattern
+    fn matches(&self, profile: &[usize]) -> bool {
+        self.pattern.len() <= profile.len()
+            && self
+                .pattern
+                .iter()
+                .zip(profile)
+                .all(|(needed, got)| got >= needed)
+    }
+}
+
+/// Everything that decides how hands are read and ranked
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Ruleset {
+    /// Card characters from weakest to strongest
+    pub alphabet: Vec<char>,
+    /// Cards that can stand in for any other card
+    pub wildcards: Vec<char>,
+    pub hand_size: usize,
+    /// From weakest to strongest, a hand gets the strongest it matches
+    pub categories: Vec<Category>,
+}
+impl Ruleset {
+    pub fn standard_categories() -> Vec<Category> {
+        vec![
+            Category::new("High card", &[1]),
+            Category::new("Pair", &[2]),
+            Category::new("Two pair", &[2, 2]),
+            Category::new("Three of a kind", &[3]),
+            Category::new("Full house", &[3, 2]),
+            Category::new("Four of a kind", &[4]),
+            Category::new("Five of a kind", &[5]),
+        ]
+    }
+
+    /// Part 1
+    pub fn standard() -> Self {
+        Ruleset {
+            alphabet: "23456789TJQKA".chars().collect(),
+            wildcards: vec![],
+            hand_size: 5,
+            categories: Self::standard_categories(),
+        }
+    }
+
+    /// Part 2, J is the weakest card but can be anything
+    pub fn jokers() -> Self {
+        Ruleset {
+            alphabet: "J23456789TQKA".chars().collect(),
+            wildcards: vec!['J'],
+            ..Self::standard()
+        }
+    }
+
+    pub fn rank(&self, card: char) -> Option<usize> {
+        self.alphabet.iter().position(|c| *c == card)
+    }
+
+    fn is_wild(&self, rank: usize) -> bool {
+        self.wildcards.contains(&self.alphabet[rank])
+    }
+
+    pub fn category_name(&self, hand: &Hand) -> &str {
+        &self.categories[hand.category].name
+    }
+
+    /// Index of the strongest category a sorted count profile matches
+    fn category_of(&self, profile: &[usize]) -> usize {
+        self.categories
+            .iter()
+            .rposition(|category| category.matches(profile))
+            .unwrap_or_default()
+    }
+
+    /// Group sizes of the hand, largest first. Wildcards all join the
+    /// largest group, which is never worse as long as bigger groups make
+    /// for stronger categories.
+    pub fn profile(&self, cards: &[usize]) -> Vec<usize> {
+        let mut counts = vec![0; self.alphabet.len()];
+        let mut wild = 0;
+        for card in cards {
+            if self.is_wild(*card) {
+                wild += 1;
+            } else {
+                counts[*card] += 1;
+            }
+        }
+
+        let mut profile: Vec<usize> = counts.into_iter().filter(|count| *count > 0).collect();
+        profile.sort_by(|a, b| b.cmp(a));
+
+        match profile.first_mut() {
+            Some(largest) => *largest += wild,
+            None if wild > 0 => profile.push(wild),
+            None => {}
+        }
+        profile
+    }
+
+    pub fn classify(&self, cards: &[usize]) -> usize {
+        self.category_of(&self.profile(cards))
+    }
+
+    pub fn parse_hand(&self, s: &str) -> Result<Hand, ParseCardError> {
+        let (cards, bid) = s.split_once(' ').ok_or(ParseCardError)?;
+
+        let cards = cards
+            .chars()
+            .map(|c| self.rank(c).ok_or(ParseCardError))
+            .collect::<Result<Vec<_>, ParseCardError>>()?;
+        if cards.len() != self.hand_size {
+            return Err(ParseCardError);
+        }
+
+        Ok(Hand {
+            category: self.classify(&cards),
+            cards,
+            bid: bid.trim().parse().map_err(|_| ParseCardError)?,
+        })
+    }
+
+    pub fn parse_hands(&self, input: &str) -> Result<Vec<Hand>, ParseCardError> {
+        input.lines().map(|line| self.parse_hand(line)).collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Hand {
+    /// Card ranks in the ruleset's alphabet
+    pub cards: Vec<usize>,
+    /// Index into the ruleset's categories
+    pub category: usize,
+    pub bid: usize,
+}
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The bid only breaks ties, so this agrees with the derived Eq
+        (self.category, &self.cards, self.bid).cmp(&(other.category, &other.cards, other.bid))
+    }
+}
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Sum of every bid multiplied by its rank
+pub fn total_winnings(mut hands: Vec<Hand>) -> usize {
+    hands.sort();
+
+    hands
+        .into_iter()
+        .enumerate()
+        .map(|(index, hand)| (index + 1) * hand.bid)
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Tries every card in place of every wildcard from `from` onwards
+    /// and keeps the best
+    fn brute_force(ruleset: &Ruleset, cards: &mut [usize], from: usize) -> usize {
+        let Some(index) = (from..cards.len()).find(|index| ruleset.is_wild(cards[*index])) else {
+            let mut counts = vec![0; ruleset.alphabet.len()];
+            for card in cards.iter() {
+                counts[*card] += 1;
+            }
+            let mut profile: Vec<usize> = counts.into_iter().filter(|count| *count > 0).collect();
+            profile.sort_by(|a, b| b.cmp(a));
+            return ruleset.category_of(&profile);
+        };
+
+        let wildcard = cards[index];
+        let mut best = None;
+        for rank in (0..ruleset.alphabet.len()).filter(|rank| !ruleset.is_wild(*rank)) {
+            cards[index] = rank;
+            best = best.max(Some(brute_force(ruleset, cards, index + 1)));
+        }
+        cards[index] = wildcard;
+
+        // Nothing to turn into, so it stays as it is
+        best.unwrap_or_else(|| brute_force(ruleset, &mut cards.to_vec(), index + 1))
+    }
+
+    /// Checks every possible hand against the brute force
+    fn exhaustive(ruleset: &Ruleset) {
+        let ranks = ruleset.alphabet.len();
+        let mut cards = vec![0; ruleset.hand_size];
+
+        for mut hand in 0..ranks.pow(ruleset.hand_size as u32) {
+            for card in cards.iter_mut() {
+                *card = hand % ranks;
+                hand /= ranks;
+            }
+            assert_eq!(
+                ruleset.classify(&cards),
+                brute_force(ruleset, &mut cards.clone(), 0),
+                "{:?}",
+                cards
+            );
+        }
+    }
+
+    #[test]
+    fn test_exhaustive_jokers() {
+        exhaustive(&Ruleset::jokers());
+    }
+
+    #[test]
+    fn test_exhaustive_two_wildcards() {
+        // Fewer ranks to keep the run short
+        exhaustive(&Ruleset {
+            alphabet: "JQ2345A".chars().collect(),
+            wildcards: vec!['J', 'Q'],
+            ..Ruleset::jokers()
+        });
+    }
+
+    #[test]
+    fn test_exhaustive_six_cards() {
+        // Fewer ranks to keep the run short
+        exhaustive(&Ruleset {
+            alphabet: "J2345A".chars().collect(),
+            hand_size: 6,
+            ..Ruleset::jokers()
+        });
+    }
+
+    fn category(ruleset: &Ruleset, hand: &str) -> String {
+        let hand = ruleset.parse_hand(hand).unwrap();
+        ruleset.category_name(&hand).to_owned()
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let ruleset = Ruleset::standard();
+        assert_eq!(ruleset.parse_hand("AKQJ 1"), Err(ParseCardError));
+        assert_eq!(ruleset.parse_hand("AKQJX 1"), Err(ParseCardError));
+        assert_eq!(ruleset.parse_hand("AKQJT x"), Err(ParseCardError));
+    }
+
+    #[test]
+    fn test_ordering_agrees_with_eq() {
+        let ruleset = Ruleset::standard();
+        let low = ruleset.parse_hand("AKQJT 1").unwrap();
+        let high = ruleset.parse_hand("AKQJT 2").unwrap();
+
+        assert_ne!(low, high);
+        assert_eq!(low.cmp(&high), Ordering::Less);
+        assert_eq!(low.cmp(&low.clone()), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_two_wildcards() {
+        let ruleset = Ruleset {
+            wildcards: vec!['J', 'Q'],
+            ..Ruleset::jokers()
+        };
+
+        assert_eq!(category(&ruleset, "AKQJT 1"), "Three of a kind");
+        assert_eq!(category(&ruleset, "QQJJA 1"), "Five of a kind");
+        assert_eq!(category(&ruleset, "QQJJJ 1"), "Five of a kind");
+    }
+
+    #[test]
+    fn test_six_card_hands() {
+        let mut ruleset = Ruleset {
+            hand_size: 6,
+            ..Ruleset::jokers()
+        };
+        ruleset
+            .categories
+            .insert(5, Category::new("Two triples", &[3, 3]));
+        ruleset
+            .categories
+            .push(Category::new("Six of a kind", &[6]));
+
+        assert_eq!(category(&ruleset, "AAKKQT 1"), "Two pair");
+        assert_eq!(category(&ruleset, "AAKKQQ 1"), "Two pair");
+        assert_eq!(category(&ruleset, "AAAKKT 1"), "Full house");
+        assert_eq!(category(&ruleset, "AAAKKK 1"), "Two triples");
+        assert_eq!(category(&ruleset, "AAAKKJ 1"), "Four of a kind");
+        assert_eq!(category(&ruleset, "AAAAKJ 1"), "Five of a kind");
+        assert_eq!(category(&ruleset, "JJJJJJ 1"), "Six of a kind");
+        assert!(ruleset.parse_hand("AAAAA 1").is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{Hand, Ruleset};
+
+/// One line of the ranking report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub rank: usize,
+    pub cards: String,
+    pub category: String,
+    /// What the wildcards were counted as, if the hand has any
+    pub substitution: Option<char>,
+    pub bid: usize,
+    pub contribution: usize,
+    /// When the hand ranked right below is in the same category, the card
+    /// position (counting from 1) that decided between them
+    pub tie_break: Option<usize>,
+}
+
+impl Ruleset {
+    /// Which card the wildcards of a hand count as. They join the largest
+    /// group, picking the strongest card when groups are equally large.
+    pub fn substitution(&self, hand: &Hand) -> Option<char> {
+        if !hand.cards.iter().any(|card| self.is_wild(*card)) {
+            return None;
+        }
+
+        let mut counts = vec![0; self.alphabet.len()];
+        for card in hand.cards.iter().filter(|card| !self.is_wild(**card)) {
+            counts[*card] += 1;
+        }
+
+        // With nothing else in the hand, the strongest card is as good as any
+        let best = (0..self.alphabet.len())
+            .filter(|rank| !self.is_wild(*rank))
+            .max_by_key(|rank| (counts[*rank], *rank))?;
+        Some(self.alphabet[best])
+    }
+}
+
+pub fn report(ruleset: &Ruleset, mut hands: Vec<Hand>) -> Vec<Row> {
+    hands.sort();
+
+    hands
+        .iter()
+        .enumerate()
+        .map(|(index, hand)| {
+            let tie_break = index
+                .checked_sub(1)
+                .map(|below| &hands[below])
+                .filter(|below| below.category == hand.category)
+                .and_then(|below| {
+                    below
+                        .cards
+                        .iter()
+                        .zip(&hand.cards)
+                        .position(|(a, b)| a != b)
+                })
+                .map(|position| position + 1);
+
+            Row {
+                rank: index + 1,
+                cards: hand
+                    .cards
+                    .iter()
+                    .map(|card| ruleset.alphabet[*card])
+                    .collect(),
+                category: ruleset.category_name(hand).to_owned(),
+                substitution: ruleset.substitution(hand),
+                bid: hand.bid,
+                contribution: (index + 1) * hand.bid,
+                tie_break,
+            }
+        })
+        .collect()
+}
+
+const HEADERS: [&str; 7] = [
+    "rank",
+    "cards",
+    "category",
+    "substitution",
+    "bid",
+    "contribution",
+    "tie break",
+];
+
+fn fields(row: &Row) -> [String; 7] {
+    let optional = |value: Option<String>| value.unwrap_or_default();
+    [
+        row.rank.to_string(),
+        row.cards.clone(),
+        row.category.clone(),
+        optional(row.substitution.map(String::from)),
+        row.bid.to_string(),
+        row.contribution.to_string(),
+        optional(row.tie_break.map(|position| position.to_string())),
+    ]
+}
+
+pub fn to_csv(rows: &[Row]) -> String {
+    let mut lines = vec![HEADERS.join(",")];
+    lines.extend(rows.iter().map(|row| fields(row).join(",")));
+    lines.join("\n")
+}
+
+pub fn to_table(rows: &[Row]) -> String {
+    let cells: Vec<[String; 7]> = rows.iter().map(fields).collect();
+
+    let widths: Vec<usize> = (0..HEADERS.len())
+        .map(|column| {
+            cells
+                .iter()
+                .map(|row| row[column].len())
+                .chain([HEADERS[column].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let format_line = |values: Vec<&str>| {
+        values
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| format!("{:<width$}", value, width = width))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_owned()
+    };
+
+    let mut lines = vec![format_line(HEADERS.to_vec())];
+    lines.push(
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-"),
+    );
+    lines.extend(
+        cells
+            .iter()
+            .map(|row| format_line(row.iter().map(String::as_str).collect())),
+    );
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_jokers_report() {
+        let ruleset = Ruleset::jokers();
+        let input = fs::read_to_string("inputs/example.txt").unwrap();
+        let rows = report(&ruleset, ruleset.parse_hands(&input).unwrap());
+
+        assert_eq!(rows.iter().map(|row| row.contribution).sum::<usize>(), 5905);
+        assert_eq!(
+            rows[4],
+            Row {
+                rank: 5,
+                cards: "KTJJT".into(),
+                category: "Four of a kind".into(),
+                substitution: Some('T'),
+                bid: 220,
+                contribution: 1100,
+                tie_break: Some(1),
+            }
+        );
+        assert_eq!(rows[0].substitution, None);
+        assert_eq!(rows[1].tie_break, None);
+
+        assert_eq!(
+            to_csv(&rows).lines().nth(3),
+            Some("3,T55J5,Four of a kind,5,684,2052,")
+        );
+        assert!(to_table(&rows)
+            .lines()
+            .any(|line| line
+                == "5    | KTJJT | Four of a kind | T            | 220 | 1100         | 1"));
+    }
+
+    #[test]
+    fn test_substitution_without_other_cards() {
+        let ruleset = Ruleset::jokers();
+        let hand = ruleset.parse_hand("JJJJJ 1").unwrap();
+        assert_eq!(ruleset.substitution(&hand), Some('A'));
+    }
+}
@@ -0,0 +1,15 @@
+use day_08::Network;
+use std::{env, fs, str::FromStr};
+
+fn main() {
+    let input = fs::read_to_string("inputs/input.txt").unwrap();
+    let network = Network::from_str(&input).unwrap();
+
+    match env::args().nth(1).as_deref() {
+        None => println!("{}", network.to_dot(None)),
+        Some(from) => match network.id(from) {
+            Some(from) => println!("{}", network.to_dot(Some(from))),
+            None => println!("usage: dot [node to start from]"),
+        },
+    }
+}
@@ -0,0 +1,374 @@
+use crate::Network;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalkError {
+    /// Walking from `start` ends up going around a cycle `length` steps long
+    /// without ever reaching an exit. `cycle` holds the names of the nodes
+    /// on it, sorted.
+    NoExit {
+        start: String,
+        length: usize,
+        cycle: Vec<String>,
+    },
+    /// Every walk reaches exits, but never all on the same step
+    NoCommonExit,
+}
+impl fmt::Display for WalkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WalkError::NoExit {
+                start,
+                length,
+                cycle,
+            } => write!(
+                f,
+                "walking from {} never reaches an exit, it goes around a cycle of {} steps through {}",
+                start,
+                length,
+                cycle.join(", ")
+            ),
+            WalkError::NoCommonExit => write!(f, "the walks are never on an exit at the same time"),
+        }
+    }
+}
+
+/// Where a walk goes when followed forever. The state of a walk is the node
+/// and the position in the instructions, and since there are finitely many
+/// of those it always ends up going around a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Walk {
+    /// Steps taken before entering the cycle
+    pub pre_period: usize,
+    /// Steps taken to go around the cycle once
+    pub period: usize,
+    /// Sorted steps, below `pre_period + period`, that end on an exit.
+    /// Never empty.
+    pub exits: Vec<usize>,
+}
+impl Walk {
+    /// Fails if the walk repeats itself before reaching any exit
+    pub fn new(
+        network: &Network,
+        start: usize,
+        is_exit: impl Fn(&str) -> bool,
+    ) -> Result<Self, WalkError> {
+        // First step each (node, instruction) state was seen on
+        let instructions = network.instructions.len();
+        let mut seen = vec![None; network.len() * instructions];
+        let mut path = vec![];
+        let mut exits = vec![];
+        let mut node = start;
+
+        loop {
+            let step = path.len();
+            let state = node * instructions + step % instructions;
+            if let Some(first) = seen[state].replace(step) {
+                if exits.is_empty() {
+                    let mut cycle: Vec<String> = path[first..]
+                        .iter()
+                        .map(|node| network.name(*node).to_owned())
+                        .collect();
+                    cycle.sort();
+                    cycle.dedup();
+
+                    return Err(WalkError::NoExit {
+                        start: network.name(start).to_owned(),
+                        length: step - first,
+                        cycle,
+                    });
+                }
+
+                return Ok(Walk {
+                    pre_period: first,
+                    period: step - first,
+                    exits,
+                });
+            }
+
+            if is_exit(network.name(node)) {
+                exits.push(step);
+            }
+            path.push(node);
+            node = network.step(node, step);
+        }
+    }
+
+    pub fn is_exit_at(&self, step: u128) -> bool {
+        let step = if step < (self.pre_period + self.period) as u128 {
+            step as usize
+        } else {
+            self.pre_period + ((step - self.pre_period as u128) % self.period as u128) as usize
+        };
+        self.exits.binary_search(&step).is_ok()
+    }
+
+    /// Exits that come around again every period
+    fn cycle_exits(&self) -> impl Iterator<Item = usize> + '_ {
+        self.exits
+            .iter()
+            .copied()
+            .filter(|step| *step >= self.pre_period)
+    }
+}
+
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (gcd, x, y) = extended_gcd(b, a % b);
+        (gcd, y, x - (a / b) * y)
+    }
+}
+
+/// Joins `x = a (mod m)` and `x = b (mod n)` into a single congruence modulo
+/// the lcm of `m` and `n`, if there is any `x` satisfying both.
+/// Keeps the numbers small as long as `n` is the smaller modulus.
+pub fn crt((a, m): (i128, i128), (b, n): (i128, i128)) -> Option<(i128, i128)> {
+    let (gcd, p, _) = extended_gcd(m, n);
+    if (b - a) % gcd != 0 {
+        return None;
+    }
+
+    let lcm = m / gcd * n;
+    let k = ((b - a) / gcd % (n / gcd)) * p % (n / gcd);
+    Some(((a + m * k).rem_euclid(lcm), lcm))
+}
+
+/// First step at which every walk is on an exit at the same time
+pub fn first_common_exit(walks: &[Walk]) -> Result<u128, WalkError> {
+    // Until every walk is in its cycle, the last one to get there is still
+    // going through exits it won't see again
+    let latest = walks
+        .iter()
+        .max_by_key(|walk| walk.pre_period)
+        .ok_or(WalkError::NoCommonExit)?;
+    for step in latest
+        .exits
+        .iter()
+        .filter(|step| **step < latest.pre_period)
+    {
+        if walks.iter().all(|walk| walk.is_exit_at(*step as u128)) {
+            return Ok(*step as u128);
+        }
+    }
+
+    // After that each walk is on an exit whenever the step is one of its
+    // cycle exits modulo its period. Every combination of those is a
+    // congruence of its own.
+    let mut congruences = vec![(0, 1)];
+    for walk in walks {
+        let period = walk.period as i128;
+        congruences = congruences
+            .into_iter()
+            .flat_map(|congruence| {
+                walk.cycle_exits()
+                    .filter_map(move |step| crt(congruence, (step as i128 % period, period)))
+            })
+            .collect();
+        congruences.sort();
+        congruences.dedup();
+    }
+
+    let settled = latest.pre_period as i128;
+    congruences
+        .into_iter()
+        .map(|(residue, modulus)| {
+            if residue >= settled {
+                residue
+            } else {
+                residue + (settled - residue + modulus - 1) / modulus * modulus
+            }
+        })
+        .min()
+        .map(|step| step as u128)
+        .ok_or(WalkError::NoCommonExit)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{fs, str::FromStr};
+
+    fn network(instructions: &str, nodes: &[(&str, &str, &str)]) -> Network {
+        let lines: Vec<String> = nodes
+            .iter()
+            .map(|(node, left, right)| format!("{} = ({}, {})", node, left, right))
+            .collect();
+        Network::from_str(&format!("{}\n\n{}", instructions, lines.join("\n"))).unwrap()
+    }
+
+    fn ghosts(network: &Network, starts: &[&str]) -> Option<u128> {
+        let walks = starts
+            .iter()
+            .map(|start| {
+                Walk::new(network, network.id(start).unwrap(), |node| {
+                    node.ends_with('Z')
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        first_common_exit(&walks).ok()
+    }
+
+    /// Moves every ghost one step at a time
+    fn brute_force(network: &Network, starts: &[&str], limit: usize) -> Option<u128> {
+        let mut nodes: Vec<usize> = starts
+            .iter()
+            .map(|start| network.id(start).unwrap())
+            .collect();
+        for step in 0..limit {
+            if nodes.iter().all(|node| network.name(*node).ends_with('Z')) {
+                return Some(step as u128);
+            }
+            for node in nodes.iter_mut() {
+                *node = network.step(*node, step);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt((2, 3), (3, 5)), Some((8, 15)));
+        assert_eq!(crt((1, 4), (3, 6)), Some((9, 12)));
+        assert_eq!(crt((1, 4), (2, 6)), None);
+        assert_eq!(crt((0, 1), (4, 7)), Some((4, 7)));
+    }
+
+    #[test]
+    fn test_walk() {
+        let input = fs::read_to_string("inputs/example_many.txt").unwrap();
+        let network = Network::from_str(&input).unwrap();
+
+        let walk = Walk::new(&network, network.id("22A").unwrap(), |node| {
+            node.ends_with('Z')
+        })
+        .unwrap();
+        assert_eq!(walk.pre_period, 1);
+        assert_eq!(walk.period, 6);
+        assert_eq!(walk.exits, vec![3, 6]);
+        assert!(walk.is_exit_at(9));
+        assert!(walk.is_exit_at(3 + 6 * 1000));
+        assert!(!walk.is_exit_at(4 + 6 * 1000));
+
+        assert_eq!(ghosts(&network, &["11A", "22A"]), Some(6));
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = fs::read_to_string("inputs/example_many.txt").unwrap();
+        let network = Network::from_str(&input).unwrap();
+        let walk =
+            |start, exit| Walk::new(&network, network.id(start).unwrap(), |node| node == exit);
+
+        assert_eq!(
+            walk("XXX", "11Z"),
+            Err(WalkError::NoExit {
+                start: "XXX".into(),
+                length: 2,
+                cycle: vec!["XXX".into()]
+            })
+        );
+
+        let error = walk("11A", "22Z").unwrap_err();
+        assert_eq!(
+            error,
+            WalkError::NoExit {
+                start: "11A".into(),
+                length: 2,
+                cycle: vec!["11B".into(), "11Z".into()]
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "walking from 11A never reaches an exit, it goes around a cycle of 2 steps through 11B, 11Z"
+        );
+
+        let apart = [walk("11A", "11B").unwrap(), walk("11A", "11Z").unwrap()];
+        assert_eq!(first_common_exit(&apart), Err(WalkError::NoCommonExit));
+    }
+
+    #[test]
+    fn test_exit_before_cycle() {
+        // 11A only passes its exit once, on the first step
+        let network = network(
+            "L",
+            &[
+                ("11A", "11Z", "11Z"),
+                ("11Z", "11B", "11B"),
+                ("11B", "11B", "11B"),
+                ("22A", "22Z", "22Z"),
+                ("22Z", "22A", "22A"),
+            ],
+        );
+        assert_eq!(ghosts(&network, &["11A", "22A"]), Some(1));
+        assert_eq!(ghosts(&network, &["11Z", "22A"]), None);
+    }
+
+    #[test]
+    fn test_several_exits_per_cycle() {
+        // Exits at 2, 3 mod 5 and at 1 mod 3, with a lead in of 1 for both
+        let network = network(
+            "L",
+            &[
+                ("11A", "11B", "11B"),
+                ("11B", "11Z", "11Z"),
+                ("11Z", "12Z", "12Z"),
+                ("12Z", "11C", "11C"),
+                ("11C", "11D", "11D"),
+                ("11D", "11B", "11B"),
+                ("22A", "22Z", "22Z"),
+                ("22Z", "22B", "22B"),
+                ("22B", "22C", "22C"),
+                ("22C", "22Z", "22Z"),
+            ],
+        );
+        assert_eq!(ghosts(&network, &["11A", "22A"]), Some(7));
+        assert_eq!(brute_force(&network, &["11A", "22A"], 100), Some(7));
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        // Small made up networks from a fixed linear congruential generator
+        let mut seed: u64 = 12345;
+        let mut random = |below: usize| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % below
+        };
+
+        for _ in 0..500 {
+            let count = 2 + random(5);
+            let names: Vec<String> = (0..count)
+                .map(|index| format!("{}{}", index, ['A', 'B', 'Z'][random(3)]))
+                .collect();
+            let nodes: Vec<(&str, &str, &str)> = names
+                .iter()
+                .map(|name| {
+                    (
+                        name.as_str(),
+                        names[random(count)].as_str(),
+                        names[random(count)].as_str(),
+                    )
+                })
+                .collect();
+            let instructions: String = (0..1 + random(3)).map(|_| ['L', 'R'][random(2)]).collect();
+            let network = network(&instructions, &nodes);
+
+            let starts: Vec<&str> = (0..1 + random(3))
+                .map(|_| names[random(count)].as_str())
+                .collect();
+            // Periods are at most 18, so anything that happens does so long
+            // before the limit
+            assert_eq!(
+                ghosts(&network, &starts),
+                brute_force(&network, &starts, 10_000),
+                "{:?} from {:?}",
+                network,
+                starts
+            );
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::Network;
+
+impl Network {
+    /// Whether each node can be reached from `from` by any mix of left and
+    /// right turns, regardless of the instructions
+    pub fn reachable_from(&self, from: usize) -> Vec<bool> {
+        let mut reached = vec![false; self.len()];
+        reached[from] = true;
+
+        let mut stack = vec![from];
+        while let Some(node) = stack.pop() {
+            for next in [self.left[node], self.right[node]] {
+                if !reached[next] {
+                    reached[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        reached
+    }
+
+    pub fn unreachable_from(&self, from: usize) -> Vec<usize> {
+        self.reachable_from(from)
+            .into_iter()
+            .enumerate()
+            .filter(|(_, reached)| !reached)
+            .map(|(node, _)| node)
+            .collect()
+    }
+
+    /// The network as a DOT digraph, with edges labelled by the turn that
+    /// takes them. Only the part reachable from `from` if one is given.
+    pub fn to_dot(&self, from: Option<usize>) -> String {
+        let included = match from {
+            Some(from) => self.reachable_from(from),
+            None => vec![true; self.len()],
+        };
+
+        let mut lines = vec!["digraph network {".to_owned()];
+        for node in (0..self.len()).filter(|node| included[*node]) {
+            lines.push(format!("    n{} [label=\"{}\"];", node, self.names[node]));
+        }
+        for node in (0..self.len()).filter(|node| included[*node]) {
+            let (left, right) = (self.left[node], self.right[node]);
+            if left == right {
+                lines.push(format!("    n{} -> n{} [label=\"LR\"];", node, left));
+            } else {
+                lines.push(format!("    n{} -> n{} [label=\"L\"];", node, left));
+                lines.push(format!("    n{} -> n{} [label=\"R\"];", node, right));
+            }
+        }
+
+        lines.push("}".to_owned());
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{fs, str::FromStr};
+
+    #[test]
+    fn test_reachability() {
+        let input = fs::read_to_string("inputs/example_many.txt").unwrap();
+        let network = Network::from_str(&input).unwrap();
+        let names = |nodes: Vec<usize>| -> Vec<&str> {
+            nodes.into_iter().map(|node| network.name(node)).collect()
+        };
+
+        assert_eq!(
+            names(network.unreachable_from(network.id("11A").unwrap())),
+            vec!["22A", "22B", "22C", "22Z"]
+        );
+        assert_eq!(
+            names(network.unreachable_from(network.id("22B").unwrap())),
+            vec!["11A", "11B", "11Z", "22A", "XXX"]
+        );
+        assert_eq!(
+            names(network.unreachable_from(network.id("XXX").unwrap())).len(),
+            7
+        );
+    }
+
+    #[test]
+    fn test_dot() {
+        let input = fs::read_to_string("inputs/example_many.txt").unwrap();
+        let network = Network::from_str(&input).unwrap();
+
+        let dot = network.to_dot(None);
+        assert!(dot.starts_with("digraph network {"));
+        assert!(dot.contains("n0 [label=\"11A\"];"));
+        assert!(dot.contains("n0 -> n1 [label=\"L\"];"));
+        assert!(dot.contains("n0 -> n7 [label=\"R\"];"));
+        assert!(dot.contains("n4 -> n5 [label=\"LR\"];"));
+
+        let reachable = network.to_dot(network.id("22B"));
+        assert!(!reachable.contains("\"11A\""));
+        assert!(!reachable.contains("n3 ->"));
+        assert!(reachable.contains("n6 -> n4 [label=\"LR\"];"));
+    }
+}
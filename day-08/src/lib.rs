@@ -0,0 +1,208 @@
+use cycle::{Walk, WalkError};
+use std::{collections::HashMap, str::FromStr};
+
+pub mod cycle;
+pub mod graph;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkParseError {
+    /// The first line has no directions on it
+    NoInstructions,
+    BadDirection(char),
+    /// Line number, counting from 1
+    BadLine(usize),
+    /// A node that is pointed at but never defined
+    UnknownNode(String),
+    /// Line number of a node that was already defined further up
+    Duplicate(usize),
+}
+
+/// Nodes are interned, so each one is an index into `names`, `left` and
+/// `right`. Ids follow the order the nodes are defined in.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Network {
+    /// 0 for left, 1 for right
+    pub instructions: Vec<usize>,
+    pub names: Vec<String>,
+    pub left: Vec<usize>,
+    pub right: Vec<usize>,
+    ids: HashMap<String, usize>,
+}
+impl FromStr for Network {
+    type Err = NetworkParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+
+        let instructions: Vec<usize> = lines
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .map(|c| match c {
+                'L' => Ok(0),
+                'R' => Ok(1),
+                _ => Err(NetworkParseError::BadDirection(c)),
+            })
+            .collect::<Result<_, _>>()?;
+        if instructions.is_empty() {
+            return Err(NetworkParseError::NoInstructions);
+        }
+
+        // The instructions are kept apart from the nodes by a blank line
+        if lines.next().is_some_and(|line| !line.is_empty()) {
+            return Err(NetworkParseError::BadLine(2));
+        }
+
+        let mut definitions = vec![];
+        for (index, line) in lines.enumerate() {
+            let line_number = index + 3;
+            let error = NetworkParseError::BadLine(line_number);
+
+            let (node, neighbours) = line.split_once(" = ").ok_or(error.clone())?;
+            let (left, right) = neighbours
+                .strip_prefix('(')
+                .and_then(|rest| rest.strip_suffix(')'))
+                .and_then(|rest| rest.split_once(", "))
+                .ok_or(error)?;
+
+            definitions.push((line_number, node, left, right));
+        }
+
+        let mut network = Network {
+            instructions,
+            ..Network::default()
+        };
+        for (line_number, node, _, _) in &definitions {
+            if network
+                .ids
+                .insert(node.to_string(), network.names.len())
+                .is_some()
+            {
+                return Err(NetworkParseError::Duplicate(*line_number));
+            }
+            network.names.push(node.to_string());
+        }
+        for (_, _, left, right) in definitions {
+            let left = network.resolve(left)?;
+            let right = network.resolve(right)?;
+            network.left.push(left);
+            network.right.push(right);
+        }
+
+        Ok(network)
+    }
+}
+impl Network {
+    fn resolve(&self, name: &str) -> Result<usize, NetworkParseError> {
+        self.id(name)
+            .ok_or_else(|| NetworkParseError::UnknownNode(name.to_owned()))
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Where `node` leads on the instruction at `step`
+    pub fn step(&self, node: usize, step: usize) -> usize {
+        match self.instructions[step % self.instructions.len()] {
+            0 => self.left[node],
+            _ => self.right[node],
+        }
+    }
+
+    /// Nodes whose name ends in `suffix`, sorted by name
+    pub fn nodes_ending_with(&self, suffix: char) -> Vec<usize> {
+        let mut nodes: Vec<usize> = (0..self.len())
+            .filter(|node| self.names[*node].ends_with(suffix))
+            .collect();
+        nodes.sort_by_key(|node| &self.names[*node]);
+        nodes
+    }
+
+    /// Steps it takes to get from `from` to any node matching `target` when
+    /// following the instructions
+    pub fn steps_to(&self, from: usize, target: impl Fn(&str) -> bool) -> Result<usize, WalkError> {
+        Ok(Walk::new(self, from, target)?.exits[0])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_parsing() {
+        let network =
+            Network::from_str(&fs::read_to_string("inputs/example1.txt").unwrap()).unwrap();
+
+        assert_eq!(network.instructions, vec![1, 0]);
+        assert_eq!(network.len(), 7);
+        assert_eq!(network.id("CCC"), Some(2));
+        assert_eq!(network.left[2], network.id("ZZZ").unwrap());
+        assert_eq!(network.name(network.step(0, 0)), "CCC");
+        assert_eq!(network.name(network.step(2, 1)), "ZZZ");
+        assert_eq!(network.id("XXX"), None);
+
+        assert_eq!(
+            Network::from_str("LX\n\nAAA = (AAA, AAA)"),
+            Err(NetworkParseError::BadDirection('X'))
+        );
+        assert_eq!(
+            Network::from_str("L\n\nAAA = (AAA, AAA)\nBBB = BBB, BBB"),
+            Err(NetworkParseError::BadLine(4))
+        );
+        assert_eq!(
+            Network::from_str("L\n\nAAA = (AAA, BBB)"),
+            Err(NetworkParseError::UnknownNode("BBB".into()))
+        );
+    }
+
+    #[test]
+    fn test_malformed_layout() {
+        assert_eq!(
+            Network::from_str("\n\nAAA = (AAA, AAA)"),
+            Err(NetworkParseError::NoInstructions)
+        );
+        assert_eq!(
+            Network::from_str(""),
+            Err(NetworkParseError::NoInstructions)
+        );
+        assert_eq!(
+            Network::from_str("L\nAAA = (AAA, AAA)"),
+            Err(NetworkParseError::BadLine(2))
+        );
+        assert_eq!(
+            Network::from_str("L\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\nAAA = (ZZZ, ZZZ)"),
+            Err(NetworkParseError::Duplicate(5))
+        );
+        assert!(Network::from_str("L\n").is_ok());
+    }
+
+    #[test]
+    fn test_steps_to() {
+        let network =
+            Network::from_str(&fs::read_to_string("inputs/example_many.txt").unwrap()).unwrap();
+        let id = |name| network.id(name).unwrap();
+
+        assert_eq!(network.steps_to(id("11A"), |node| node == "11Z"), Ok(2));
+        assert_eq!(
+            network.steps_to(id("22A"), |node| node.ends_with('Z')),
+            Ok(3)
+        );
+        assert_eq!(network.steps_to(id("22B"), |node| node == "22B"), Ok(0));
+        assert!(network.steps_to(id("11A"), |node| node == "22Z").is_err());
+    }
+}
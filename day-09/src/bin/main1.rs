@@ -1,38 +1,27 @@
-use std::fs;
+use day_09::{parse_sequences, SequenceError};
+use num_rational::BigRational;
+use std::{env, fs};
 
-fn main() {
+fn main() -> Result<(), String> {
     let input = fs::read_to_string("inputs/input.txt").unwrap();
-    println!("{}", compute(input));
-}
+    // Degree to fit the histories that aren't polynomials with, if any
+    let fallback = env::args()
+        .nth(1)
+        .map(|degree| degree.parse().expect("The degree should be a number"));
 
-fn compute(input: String) -> String {
-    input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|num| num.parse::<isize>().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .fold(0, |acc, new| {
-            acc + {
-                let mut stack = vec![new.clone()];
-                while stack.last().unwrap().iter().any(|num| *num != 0) {
-                    // All of them are not zeros, make a new layer
-                    let prev = stack.last().unwrap().clone();
-                    assert!(prev.len() > 1);
-                    stack.push(prev.windows(2).fold(vec![], |mut acc, window| {
-                        acc.push(window[1] - window[0]);
-                        acc
-                    }))
-                }
+    println!(
+        "{}",
+        compute(input, fallback).map_err(|error| error.to_string())?
+    );
+    Ok(())
+}
 
-                stack
-                    .into_iter()
-                    .rev()
-                    .fold(0, |curr, layer| curr + layer.last().unwrap())
-            }
-        })
-        .to_string()
+fn compute(input: String, fallback: Option<usize>) -> Result<String, SequenceError> {
+    Ok(parse_sequences(&input, fallback)?
+        .iter()
+        .map(|sequence| sequence.extrapolate(1))
+        .sum::<BigRational>()
+        .to_string())
 }
 
 #[cfg(test)]
@@ -42,6 +31,6 @@ mod test {
     #[test]
     fn test_example() {
         let input = fs::read_to_string("inputs/example.txt").unwrap();
-        assert_eq!("114", compute(input));
+        assert_eq!(Ok("114".into()), compute(input, None));
     }
 }
@@ -0,0 +1,388 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ParseSequenceError;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SequenceError {
+    /// Line number, counting from 1
+    Parse(usize),
+    /// The differences on this line never settle on zeros, so it isn't a
+    /// polynomial of a degree below its length - 1
+    NotPolynomial(usize),
+}
+impl fmt::Display for SequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SequenceError::Parse(line) => write!(f, "line {} isn't a list of numbers", line),
+            SequenceError::NotPolynomial(line) => write!(
+                f,
+                "line {} isn't a polynomial, pass a degree to fit it with",
+                line
+            ),
+        }
+    }
+}
+
+/// A history of readings, seen as a polynomial with the first reading at
+/// x = 0. Goes through all of the readings unless it's a least squares fit.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Sequence {
+    /// First entry of each row of the difference table, down to the last
+    /// row that isn't all zeros. These are the coefficients of the Newton
+    /// form, `sum(differences[k] * binomial(x, k))`.
+    pub differences: Vec<BigRational>,
+    pub len: usize,
+}
+impl FromStr for Sequence {
+    type Err = ParseSequenceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Sequence::new(&parse_values(s)?))
+    }
+}
+impl Sequence {
+    pub fn new(values: &[BigInt]) -> Self {
+        let values: Vec<BigRational> = values.iter().cloned().map(BigRational::from).collect();
+        Self::through(values)
+    }
+
+    /// Builds the difference table of `values`
+    fn through(values: Vec<BigRational>) -> Self {
+        let len = values.len();
+        let mut differences = vec![];
+
+        let mut row = values;
+        while row.iter().any(|value| *value != BigRational::default()) {
+            differences.push(row[0].clone());
+            row = row
+                .windows(2)
+                .map(|window| &window[1] - &window[0])
+                .collect();
+        }
+
+        Sequence { differences, len }
+    }
+
+    /// The polynomial of at most `degree` closest to the readings in the
+    /// least squares sense. Goes through all of them once `degree` reaches
+    /// the length - 1.
+    pub fn least_squares(values: &[BigInt], degree: usize) -> Self {
+        let terms = (degree + 1).min(values.len());
+        if terms == 0 {
+            return Sequence {
+                differences: vec![],
+                len: values.len(),
+            };
+        }
+
+        // Normal equations, sum(x^(i + j)) c_j = sum(x^i y) for each i
+        let powers: Vec<Vec<BigRational>> = (0..values.len())
+            .map(|x| {
+                (0..2 * terms - 1)
+                    .map(|power| BigRational::from(BigInt::from(x).pow(power as u32)))
+                    .collect()
+            })
+            .collect();
+        let mut system: Vec<Vec<BigRational>> = (0..terms)
+            .map(|row| {
+                let mut equation: Vec<BigRational> = (0..terms)
+                    .map(|column| powers.iter().map(|x| &x[row + column]).sum())
+                    .collect();
+                equation.push(
+                    powers
+                        .iter()
+                        .zip(values)
+                        .map(|(x, y)| &x[row] * BigRational::from(y.clone()))
+                        .sum(),
+                );
+                equation
+            })
+            .collect();
+
+        let coefficients = solve(&mut system);
+        let samples = (0..terms)
+            .map(|x| {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(BigRational::default(), |acc, coefficient| {
+                        acc * BigInt::from(x) + coefficient
+                    })
+            })
+            .collect();
+
+        Sequence {
+            len: values.len(),
+            ..Self::through(samples)
+        }
+    }
+
+    /// Whether the differences reach a row of zeros before running out,
+    /// which they always do for a polynomial of a degree below len - 1
+    pub fn settles(&self) -> bool {
+        self.differences.len() < self.len || self.len == 0
+    }
+
+    /// None for a sequence of zeros
+    pub fn degree(&self) -> Option<usize> {
+        self.differences.len().checked_sub(1)
+    }
+
+    /// Value of the polynomial at `x`, where the readings are at 0..len
+    pub fn at(&self, x: isize) -> BigRational {
+        let x = BigInt::from(x);
+        let mut binomial = BigInt::from(1);
+        let mut value = BigRational::default();
+
+        for (k, difference) in self.differences.iter().enumerate() {
+            value += difference * BigRational::from(binomial.clone());
+            // binomial(x, k + 1) from binomial(x, k), always divides evenly
+            binomial = binomial * (&x - k) / (k + 1);
+        }
+
+        value
+    }
+
+    /// The value `k` steps after the last reading, or `-k` steps before the
+    /// first one when `k` is negative. 0 is the last reading.
+    pub fn extrapolate(&self, k: isize) -> BigRational {
+        if k >= 0 {
+            self.at(self.len as isize - 1 + k)
+        } else {
+            self.at(k)
+        }
+    }
+
+    /// Coefficients of the polynomial from the constant term up. Any
+    /// polynomial through the readings of at most this degree, such as the
+    /// Lagrange form, expands to the same thing.
+    pub fn coefficients(&self) -> Vec<BigRational> {
+        let mut coefficients = vec![BigRational::default(); self.differences.len()];
+        // x (x - 1) ... (x - k + 1) and k!
+        let mut falling = vec![BigInt::from(1)];
+        let mut factorial = BigInt::from(1);
+
+        for (k, difference) in self.differences.iter().enumerate() {
+            for (power, coefficient) in falling.iter().enumerate() {
+                coefficients[power] +=
+                    difference * BigRational::new(coefficient.clone(), factorial.clone());
+            }
+
+            // Multiply by (x - k)
+            let mut next = vec![BigInt::ZERO; falling.len() + 1];
+            for (power, coefficient) in falling.iter().enumerate() {
+                next[power + 1] += coefficient;
+                next[power] -= coefficient * k;
+            }
+            falling = next;
+            factorial *= k + 1;
+        }
+
+        coefficients
+    }
+}
+
+/// Gauss-Jordan elimination on an augmented matrix with a unique solution
+fn solve(system: &mut [Vec<BigRational>]) -> Vec<BigRational> {
+    let size = system.len();
+    for column in 0..size {
+        let pivot = (column..size)
+            .find(|row| system[*row][column] != BigRational::default())
+            .expect("The normal equations of distinct points are never singular");
+        system.swap(column, pivot);
+
+        let lead = system[column][column].clone();
+        for value in system[column].iter_mut() {
+            *value /= &lead;
+        }
+        let pivot_row = system[column].clone();
+        for (row, equation) in system.iter_mut().enumerate() {
+            if row == column {
+                continue;
+            }
+            let factor = equation[column].clone();
+            for (value, pivot_value) in equation.iter_mut().zip(&pivot_row) {
+                *value -= &factor * pivot_value;
+            }
+        }
+    }
+
+    system.iter().map(|row| row[size].clone()).collect()
+}
+
+fn parse_values(line: &str) -> Result<Vec<BigInt>, ParseSequenceError> {
+    line.split_whitespace()
+        .map(|num| num.parse::<BigInt>().map_err(|_| ParseSequenceError))
+        .collect()
+}
+
+/// One sequence per line. The ones that don't settle are an error, unless
+/// there's a degree to fall back to fitting them with.
+pub fn parse_sequences(
+    input: &str,
+    fallback: Option<usize>,
+) -> Result<Vec<Sequence>, SequenceError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let values = parse_values(line).map_err(|_| SequenceError::Parse(index + 1))?;
+            let sequence = Sequence::new(&values);
+
+            match fallback {
+                _ if sequence.settles() => Ok(sequence),
+                Some(degree) => Ok(Sequence::least_squares(&values, degree)),
+                None => Err(SequenceError::NotPolynomial(index + 1)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    fn sequence(values: &[isize]) -> Sequence {
+        Sequence::new(
+            &values
+                .iter()
+                .map(|value| BigInt::from(*value))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn whole(value: isize) -> BigRational {
+        BigRational::from(BigInt::from(value))
+    }
+
+    fn ratio(numer: isize, denom: isize) -> BigRational {
+        BigRational::new(numer.into(), denom.into())
+    }
+
+    #[test]
+    fn test_example() {
+        let sequences =
+            parse_sequences(&fs::read_to_string("inputs/example.txt").unwrap(), None).unwrap();
+
+        let next: Vec<BigRational> = sequences.iter().map(|seq| seq.extrapolate(1)).collect();
+        assert_eq!(next, vec![whole(18), whole(28), whole(68)]);
+        let previous: Vec<BigRational> = sequences.iter().map(|seq| seq.extrapolate(-1)).collect();
+        assert_eq!(previous, vec![whole(-3), whole(0), whole(5)]);
+
+        assert_eq!(sequences[0].degree(), Some(1));
+        assert_eq!(sequences[2].degree(), Some(3));
+        assert_eq!(
+            sequences[2].differences,
+            vec![whole(10), whole(3), whole(0), whole(2)]
+        );
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // x^2
+        let squares = sequence(&[0, 1, 4, 9]);
+        assert_eq!(squares.extrapolate(0), whole(9));
+        assert_eq!(squares.extrapolate(3), whole(36));
+        assert_eq!(squares.extrapolate(-4), whole(16));
+        assert_eq!(squares.at(1000), whole(1_000_000));
+
+        let zeros = sequence(&[0, 0, 0]);
+        assert_eq!(zeros.degree(), None);
+        assert_eq!(zeros.extrapolate(5), whole(0));
+        assert!(zeros.coefficients().is_empty());
+    }
+
+    #[test]
+    fn test_no_overflow() {
+        // x^20 is already past isize at x = 9
+        let values: Vec<BigInt> = (0..21).map(|x| BigInt::from(x).pow(20)).collect();
+        let high = Sequence::new(&values);
+
+        assert_eq!(high.degree(), Some(20));
+        assert_eq!(high.extrapolate(1), BigInt::from(21).pow(20).into());
+        assert_eq!(high.at(-100), BigInt::from(100).pow(20).into());
+    }
+
+    #[test]
+    fn test_coefficients() {
+        // (x^3 - x) / 6 + 2
+        let cubic = sequence(&[2, 2, 3, 6, 12]);
+        assert_eq!(
+            cubic.coefficients(),
+            vec![whole(2), ratio(-1, 6), whole(0), ratio(1, 6)]
+        );
+
+        let input = fs::read_to_string("inputs/input.txt").unwrap();
+        for seq in parse_sequences(&input, None).unwrap().iter().take(20) {
+            let coefficients = seq.coefficients();
+            for x in -3..25 {
+                let value = coefficients
+                    .iter()
+                    .rev()
+                    .fold(BigRational::default(), |acc, coefficient| {
+                        acc * BigInt::from(x) + coefficient
+                    });
+                assert_eq!(value, seq.at(x));
+            }
+        }
+    }
+
+    #[test]
+    fn test_not_polynomial() {
+        let input = "1 2 3\n1 2 4 8 16\n0 0 1";
+        assert_eq!(
+            parse_sequences(input, None),
+            Err(SequenceError::NotPolynomial(2))
+        );
+        assert_eq!(
+            parse_sequences("1 2 3\n1 x", None),
+            Err(SequenceError::Parse(2))
+        );
+        assert_eq!(
+            SequenceError::NotPolynomial(2).to_string(),
+            "line 2 isn't a polynomial, pass a degree to fit it with"
+        );
+        assert!(!sequence(&[5]).settles());
+        assert!(sequence(&[0]).settles());
+        assert!(!sequence(&[1, 2, 4]).settles());
+        assert!(sequence(&[]).settles());
+
+        // Only the lines that don't settle get fitted
+        let fitted = parse_sequences(input, Some(1)).unwrap();
+        assert_eq!(fitted[0], sequence(&[1, 2, 3]));
+        assert_eq!(fitted[1].degree(), Some(1));
+        assert_eq!(fitted[2].degree(), Some(1));
+    }
+
+    #[test]
+    fn test_least_squares() {
+        // Best line through (0, 0), (1, 1), (2, 0) is flat at 1/3
+        let values: Vec<BigInt> = [0, 1, 0].into_iter().map(BigInt::from).collect();
+        let flat = Sequence::least_squares(&values, 1);
+        assert_eq!(flat.degree(), Some(0));
+        assert_eq!(flat.extrapolate(10), ratio(1, 3));
+
+        // Best line through 1, 2, 4, 8, 16 is 18x / 5 - 1
+        let doubling: Vec<BigInt> = [1, 2, 4, 8, 16].into_iter().map(BigInt::from).collect();
+        let line = Sequence::least_squares(&doubling, 1);
+        assert_eq!(line.coefficients(), vec![whole(-1), ratio(18, 5)]);
+        assert_eq!(line.extrapolate(1), whole(17));
+
+        // A degree that's high enough goes through every reading
+        assert_eq!(
+            Sequence::least_squares(&doubling, 4),
+            Sequence::new(&doubling)
+        );
+        assert_eq!(
+            Sequence::least_squares(&doubling, 10),
+            Sequence::new(&doubling)
+        );
+        // Fitting a polynomial with its own degree gives it back
+        let cubic: Vec<BigInt> = [2, 2, 3, 6, 12].into_iter().map(BigInt::from).collect();
+        assert_eq!(Sequence::least_squares(&cubic, 3), Sequence::new(&cubic));
+    }
+}
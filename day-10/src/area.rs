@@ -0,0 +1,111 @@
+use crate::{dimensions, Grid};
+use std::collections::HashSet;
+
+/// Twice the area of the polygon with the centers of the loop's cells as
+/// corners, from the shoelace formula
+pub fn double_area(pipe_loop: &[(usize, usize)]) -> usize {
+    let signed: isize = pipe_loop
+        .iter()
+        .zip(pipe_loop.iter().cycle().skip(1))
+        .map(|(a, b)| a.0 as isize * b.1 as isize - b.0 as isize * a.1 as isize)
+        .sum();
+    signed.unsigned_abs()
+}
+
+/// Pick's theorem says area = inside + boundary / 2 - 1, and every cell of
+/// the loop is a point on the boundary
+pub fn pick(pipe_loop: &[(usize, usize)]) -> usize {
+    (double_area(pipe_loop) + 2 - pipe_loop.len()) / 2
+}
+
+/// Tiles off the loop that it encloses, found by walking along each row
+/// keeping track of whether it's inside. Only loop cells with a pipe going
+/// up flip that, so L-7 crosses the loop once and L-J not at all.
+pub fn inside_tiles(grid: &Grid, pipe_loop: &[(usize, usize)]) -> HashSet<(usize, usize)> {
+    // Read from the loop itself, so the start needs no special treatment
+    let len = pipe_loop.len();
+    let goes_up: HashSet<(usize, usize)> = (0..len)
+        .filter(|index| {
+            let cell = pipe_loop[*index];
+            [
+                pipe_loop[(index + len - 1) % len],
+                pipe_loop[(index + 1) % len],
+            ]
+            .iter()
+            .any(|other| other.0 + 1 == cell.0 && other.1 == cell.1)
+        })
+        .map(|index| pipe_loop[index])
+        .collect();
+    let on_loop: HashSet<(usize, usize)> = pipe_loop.iter().copied().collect();
+
+    let (rows, columns) = dimensions(grid);
+    let mut tiles = HashSet::new();
+    for row in 0..rows {
+        let mut inside = false;
+        for column in 0..columns {
+            if goes_up.contains(&(row, column)) {
+                inside = !inside;
+            } else if inside && !on_loop.contains(&(row, column)) {
+                tiles.insert((row, column));
+            }
+        }
+    }
+    tiles
+}
+
+pub fn scanline(grid: &Grid, pipe_loop: &[(usize, usize)]) -> usize {
+    inside_tiles(grid, pipe_loop).len()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{flood, parse_grid, PipeLoop};
+    use std::fs;
+
+    #[test]
+    fn test_square() {
+        // 3x3 ring of pipes around a single tile
+        let ring = [
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (1, 2),
+            (2, 2),
+            (2, 1),
+            (2, 0),
+            (1, 0),
+        ];
+        assert_eq!(double_area(&ring), 8);
+        assert_eq!(pick(&ring), 1);
+
+        let reversed: Vec<_> = ring.iter().rev().copied().collect();
+        assert_eq!(pick(&reversed), 1);
+    }
+
+    #[test]
+    fn test_methods_agree() {
+        for (file, expected) in [
+            ("inputs/example1-1.txt", 1),
+            ("inputs/example1-2.txt", 1),
+            ("inputs/example2-1.txt", 4),
+            ("inputs/example2-2.txt", 8),
+            ("inputs/example2-3.txt", 10),
+        ] {
+            let grid = parse_grid(fs::read_to_string(file).unwrap());
+            let pipe_loop = PipeLoop::find(&grid).unwrap().cells;
+
+            assert_eq!(flood::enclosed(&grid, &pipe_loop), expected, "{}", file);
+            assert_eq!(pick(&pipe_loop), expected, "{}", file);
+            assert_eq!(scanline(&grid, &pipe_loop), expected, "{}", file);
+        }
+    }
+
+    #[test]
+    fn test_input() {
+        let grid = parse_grid(fs::read_to_string("inputs/input.txt").unwrap());
+        let pipe_loop = PipeLoop::find(&grid).unwrap().cells;
+
+        assert_eq!(pick(&pipe_loop), scanline(&grid, &pipe_loop));
+    }
+}
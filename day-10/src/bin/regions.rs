@@ -0,0 +1,22 @@
+use day_10::{classify::Classification, flood::draw_grid, parse_grid, PipeLoop};
+use std::fs;
+
+fn main() -> Result<(), String> {
+    let input = fs::read_to_string("inputs/input.txt").unwrap();
+    let grid = parse_grid(input);
+    let pipe_loop = PipeLoop::find(&grid).map_err(|error| error.to_string())?;
+    let classification = Classification::new(&grid, &pipe_loop);
+
+    draw_grid(&classification.labels);
+    for region in &classification.regions {
+        println!(
+            "{:?}: {} tiles {}, {} of them junk",
+            region.first,
+            region.size,
+            if region.inside { "inside" } else { "outside" },
+            region.junk
+        );
+    }
+
+    Ok(())
+}
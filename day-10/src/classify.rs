@@ -0,0 +1,189 @@
+use crate::{area::inside_tiles, flood::regions, Grid, PipeLoop, Tile};
+use std::{collections::HashMap, fmt::Display};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Label {
+    /// Part of the loop, with the pipe under S filled in
+    Loop(Tile),
+    Inside,
+    Outside,
+    /// A pipe that isn't connected to the loop
+    Junk {
+        inside: bool,
+    },
+}
+impl Label {
+    pub fn is_inside(&self) -> bool {
+        matches!(self, Label::Inside | Label::Junk { inside: true })
+    }
+}
+impl Display for Label {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Label::Loop(tile) => write!(f, "{}", tile),
+            Label::Inside => f.write_str("I"),
+            Label::Outside => f.write_str("O"),
+            Label::Junk { inside: true } => f.write_str("i"),
+            Label::Junk { inside: false } => f.write_str("o"),
+        }
+    }
+}
+
+/// Tiles off the loop that can reach each other without crossing it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// The first tile in reading order
+    pub first: (usize, usize),
+    pub inside: bool,
+    pub size: usize,
+    /// How many of the tiles are junk pipes
+    pub junk: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Classification {
+    pub labels: HashMap<(usize, usize), Label>,
+    /// In reading order of their first tile
+    pub regions: Vec<Region>,
+}
+impl Classification {
+    /// Every tile gets a label, in time linear in the size of the grid
+    pub fn new(grid: &Grid, pipe_loop: &PipeLoop) -> Self {
+        let inside = inside_tiles(grid, &pipe_loop.cells);
+
+        let mut labels: HashMap<(usize, usize), Label> = pipe_loop
+            .cells
+            .iter()
+            .map(|cell| match grid[cell] {
+                Tile::Start => (*cell, Label::Loop(pipe_loop.start_tile)),
+                tile => (*cell, Label::Loop(tile)),
+            })
+            .collect();
+
+        let regions = regions(grid, &pipe_loop.cells)
+            .into_iter()
+            .map(|area| {
+                let is_inside = inside.contains(&area[0]);
+                let mut junk = 0;
+                for tile in &area {
+                    let label = match grid[tile] {
+                        Tile::Ground if is_inside => Label::Inside,
+                        Tile::Ground => Label::Outside,
+                        _ => {
+                            junk += 1;
+                            Label::Junk { inside: is_inside }
+                        }
+                    };
+                    labels.insert(*tile, label);
+                }
+
+                Region {
+                    first: area.iter().copied().min().unwrap(),
+                    inside: is_inside,
+                    size: area.len(),
+                    junk,
+                }
+            })
+            .collect();
+
+        Classification { labels, regions }
+    }
+
+    pub fn inside_count(&self) -> usize {
+        self.labels
+            .values()
+            .filter(|label| label.is_inside())
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{area::pick, flood::render_grid, parse_grid};
+    use std::fs;
+
+    fn classify(file: &str) -> Classification {
+        let grid = parse_grid(fs::read_to_string(file).unwrap());
+        Classification::new(&grid, &PipeLoop::find(&grid).unwrap())
+    }
+
+    #[test]
+    fn test_render() {
+        let classification = classify("inputs/example2-1.txt");
+
+        assert_eq!(
+            render_grid(&classification.labels),
+            [
+                "OOOOOOOOOOO",
+                "OF-------7O",
+                "O|F-----7|O",
+                "O||OOOOO||O",
+                "O||OOOOO||O",
+                "O|L-7OF-J|O",
+                "O|II|O|II|O",
+                "OL--JOL--JO",
+                "OOOOOOOOOOO",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            classification.regions,
+            vec![
+                Region {
+                    first: (0, 0),
+                    inside: false,
+                    size: 49,
+                    junk: 0
+                },
+                Region {
+                    first: (6, 2),
+                    inside: true,
+                    size: 2,
+                    junk: 0
+                },
+                Region {
+                    first: (6, 7),
+                    inside: true,
+                    size: 2,
+                    junk: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_junk() {
+        let classification = classify("inputs/example2-3.txt");
+
+        assert_eq!(classification.inside_count(), 10);
+        assert_eq!(
+            classification.labels[&(0, 0)],
+            Label::Junk { inside: false }
+        );
+        assert_eq!(
+            classification.labels[&(3, 14)],
+            Label::Junk { inside: true }
+        );
+        assert_eq!(classification.labels[&(4, 10)], Label::Inside);
+        assert_eq!(
+            classification
+                .regions
+                .iter()
+                .filter(|region| region.inside)
+                .map(|region| region.size)
+                .sum::<usize>(),
+            10
+        );
+    }
+
+    #[test]
+    fn test_input() {
+        let grid = parse_grid(fs::read_to_string("inputs/input.txt").unwrap());
+        let pipe_loop = PipeLoop::find(&grid).unwrap();
+        let classification = Classification::new(&grid, &pipe_loop);
+
+        assert_eq!(classification.labels.len(), grid.len());
+        assert_eq!(classification.inside_count(), pick(&pipe_loop.cells));
+    }
+}
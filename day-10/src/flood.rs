@@ -0,0 +1,162 @@
+use crate::{dimensions, Grid, Tile};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    iter::once,
+};
+
+/// Tiles enclosed by the loop, by flood filling a grid scaled up 2x so that
+/// the gaps between neighbouring pipes become passable
+pub fn enclosed(grid: &Grid, pipe_loop: &[(usize, usize)]) -> usize {
+    // Scale the whole thing up
+    // This way there is a clear path out for anything not included
+    let (scaled_grid, scaled_loop) = scaled_grid(grid, pipe_loop);
+
+    let orig_areas = regions(grid, pipe_loop);
+    let scaled_areas = regions(&scaled_grid, &scaled_loop);
+    let scaled_inside: HashSet<(usize, usize)> =
+        inside(&scaled_areas, &scaled_grid).into_iter().collect();
+
+    orig_areas
+        .into_iter()
+        .filter(|area| {
+            let point = area[0];
+            scaled_inside.contains(&(point.0 * 2, point.1 * 2))
+        })
+        .fold(0, |acc, area| acc + area.len())
+}
+
+pub fn inside(areas: &Vec<Vec<(usize, usize)>>, grid: &Grid) -> Vec<(usize, usize)> {
+    // This will find one of the inside areas
+    // For the scaled version, it should be the only one.
+
+    // Bottom left corner
+    let max_coord = grid.keys().max_by_key(|(row, col)| row * col).unwrap();
+    for area in areas {
+        if !area.iter().any(|coord| {
+            coord.0 == 0 || coord.0 == max_coord.0 || coord.1 == 0 || coord.1 == max_coord.1
+        }) {
+            return area.to_owned();
+        }
+    }
+    // Didn't find an area that doesn't touch a wall.
+    vec![]
+}
+
+/// One line per row, works for tiles as well as anything else that prints
+/// as a single character
+pub fn render_grid<T: Display>(grid: &HashMap<(usize, usize), T>) -> String {
+    let (rows, columns) = dimensions(grid);
+    (0..rows)
+        .map(|row| {
+            (0..columns)
+                .map(|col| grid[&(row, col)].to_string())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn draw_grid<T: Display>(grid: &HashMap<(usize, usize), T>) {
+    println!("{}", render_grid(grid));
+}
+
+/// Groups of tiles off the loop that can reach each other, in reading order
+/// of their first tile. Every tile is looked at a constant number of times.
+pub fn regions(grid: &Grid, pipe_loop: &[(usize, usize)]) -> Vec<Vec<(usize, usize)>> {
+    let mut seen: HashSet<(usize, usize)> = pipe_loop.iter().copied().collect();
+    let mut areas = vec![];
+
+    let (rows, columns) = dimensions(grid);
+    for fill_start in (0..rows).flat_map(|row| (0..columns).map(move |col| (row, col))) {
+        if !grid.contains_key(&fill_start) || !seen.insert(fill_start) {
+            continue;
+        }
+
+        let mut area = vec![];
+        let mut to_check = vec![fill_start];
+        while let Some(next) = to_check.pop() {
+            area.push(next);
+            to_check.extend(
+                Tile::neighbors(next)
+                    .into_iter()
+                    // The neighbors are not bounded by the
+                    // size, just enought that they don't
+                    // crash because usize went negative
+                    .filter(|n| grid.contains_key(n))
+                    .filter(|n| seen.insert(*n)),
+            );
+        }
+        areas.push(area);
+    }
+    areas
+}
+
+pub fn scaled_grid(grid: &Grid, pipe_loop: &[(usize, usize)]) -> (Grid, Vec<(usize, usize)>) {
+    // All interpolated pipes are straight
+    let mut interp_ns = HashSet::new();
+    let mut interp_ew = HashSet::new();
+
+    let starting_point = pipe_loop[0];
+
+    let scaled_loop = pipe_loop
+        .iter()
+        .chain(once(&starting_point)) // Added to bridge from last to first
+        .map(|coord| (coord.0 * 2, coord.1 * 2)) // Scale
+        .fold(vec![], |mut acc, coord| {
+            // Interpolate
+
+            let Some(last) = acc.last() else {
+                acc.push(coord);
+                return acc;
+            };
+
+            let ln: HashSet<_> = Tile::neighbors(*last).into_iter().collect();
+            let nn: HashSet<_> = Tile::neighbors(coord).into_iter().collect();
+            let overlap: Vec<_> = ln.intersection(&nn).cloned().collect();
+            assert_eq!(overlap.len(), 1);
+            let new_coord = *overlap.last().unwrap();
+
+            if last.0 == coord.0 {
+                interp_ew.insert(new_coord);
+            } else {
+                interp_ns.insert(new_coord);
+            }
+
+            acc.push(new_coord);
+            acc.push(coord);
+            acc
+        });
+
+    assert_eq!(scaled_loop.first().unwrap(), scaled_loop.last().unwrap());
+
+    let scaled_grid = grid
+        .iter()
+        .flat_map(|(coord, tile)| {
+            let orig_coord = (coord.0 * 2, coord.1 * 2);
+            let orig = (orig_coord, tile.to_owned());
+            let ext = vec![
+                (orig_coord.0 + 1, orig_coord.1),
+                (orig_coord.0, orig_coord.1 + 1),
+                (orig_coord.0 + 1, orig_coord.1 + 1),
+            ];
+
+            // Realized after making this that this isn't necessary
+            // Keeping it because it may make debugging look prettier
+            vec![orig].into_iter().chain(ext.into_iter().map(|c| {
+                (
+                    c,
+                    if interp_ew.contains(&c) {
+                        Tile::EW
+                    } else if interp_ns.contains(&c) {
+                        Tile::NS
+                    } else {
+                        Tile::Ground
+                    },
+                )
+            }))
+        })
+        .collect();
+
+    (scaled_grid, scaled_loop)
+}
@@ -0,0 +1,350 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+pub mod area;
+pub mod classify;
+pub mod flood;
+
+pub type Grid = HashMap<(usize, usize), Tile>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    NS,
+    EW,
+    NE,
+    NW,
+    SW,
+    SE,
+    Ground,
+    Start,
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Tile::NS => "|",
+            Tile::EW => "-",
+            Tile::NE => "L",
+            Tile::NW => "J",
+            Tile::SW => "7",
+            Tile::SE => "F",
+            Tile::Ground => ".",
+            Tile::Start => "S",
+        })
+    }
+}
+impl FromStr for Tile {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 1 {
+            return Err(());
+        };
+
+        Ok(match s {
+            "|" => Tile::NS,
+            "-" => Tile::EW,
+            "L" => Tile::NE,
+            "J" => Tile::NW,
+            "7" => Tile::SW,
+            "F" => Tile::SE,
+            "." => Tile::Ground,
+            "S" => Tile::Start,
+            _ => return Err(()),
+        })
+    }
+}
+impl Tile {
+    pub fn connections(&self, coord: (usize, usize)) -> Vec<(usize, usize)> {
+        let up = (coord.0.max(1) - 1, coord.1);
+        let down = (coord.0 + 1, coord.1);
+        let left = (coord.0, coord.1.max(1) - 1);
+        let right = (coord.0, coord.1 + 1);
+
+        match self {
+            Tile::NS => vec![up, down],
+            Tile::EW => vec![left, right],
+            Tile::NE => vec![up, right],
+            Tile::NW => vec![up, left],
+            Tile::SW => vec![down, left],
+            Tile::SE => vec![down, right],
+            Tile::Ground => vec![],
+            Tile::Start => vec![up, down, left, right],
+        }
+    }
+
+    pub fn neighbors(coord: (usize, usize)) -> Vec<(usize, usize)> {
+        // logically, they should probably call each other the other way
+        Tile::Start.connections(coord)
+    }
+}
+
+/// Number of rows and columns
+pub fn dimensions<T>(grid: &HashMap<(usize, usize), T>) -> (usize, usize) {
+    grid.keys().fold((0, 0), |(rows, columns), (row, col)| {
+        (rows.max(row + 1), columns.max(col + 1))
+    })
+}
+
+pub fn parse_grid(input: String) -> Grid {
+    input
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| Tile::from_str(&c.to_string()).unwrap())
+                .enumerate()
+                .collect::<Vec<_>>()
+        })
+        .enumerate()
+        .fold(HashMap::new(), |mut acc, (row, tiles)| {
+            for (col, tile) in tiles.into_iter() {
+                acc.insert((row, col), tile);
+            }
+            acc
+        })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipeError {
+    NoStart,
+    /// S needs exactly two neighbours leading into it to be part of a loop.
+    /// These are the ones that do.
+    BadStart {
+        at: (usize, usize),
+        connected: Vec<(usize, usize)>,
+    },
+    /// The pipe at `at` leads off the grid, into the ground or into a pipe
+    /// that doesn't lead back
+    DeadEnd {
+        at: (usize, usize),
+    },
+    /// The loop reaches a tile it could go on from in more than one way,
+    /// such as a second S
+    Branch {
+        at: (usize, usize),
+    },
+}
+impl Display for PipeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PipeError::NoStart => write!(f, "there is no S on the grid"),
+            PipeError::BadStart { at, connected } => write!(
+                f,
+                "the S at {:?} has {} pipes leading into it instead of 2",
+                at,
+                connected.len()
+            ),
+            PipeError::DeadEnd { at } => write!(f, "the loop comes to a dead end at {:?}", at),
+            PipeError::Branch { at } => write!(f, "the loop branches at {:?}", at),
+        }
+    }
+}
+
+/// The loop of pipes going through S
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipeLoop {
+    /// Starting from S, in the order they're connected
+    pub cells: Vec<(usize, usize)>,
+    /// The pipe hidden under S
+    pub start_tile: Tile,
+}
+impl PipeLoop {
+    pub fn find(grid: &Grid) -> Result<Self, PipeError> {
+        // The first one, if there happen to be several
+        let start = grid
+            .iter()
+            .filter(|(_, tile)| **tile == Tile::Start)
+            .map(|(coord, _)| *coord)
+            .min()
+            .ok_or(PipeError::NoStart)?;
+
+        // Neighbours that lead into S. Moving off the top or left edge gives
+        // back the same coordinate, so those are left out.
+        let connected: Vec<(usize, usize)> = Tile::neighbors(start)
+            .into_iter()
+            .filter(|coord| *coord != start)
+            .filter(|coord| {
+                grid.get(coord)
+                    .is_some_and(|tile| tile.connections(*coord).contains(&start))
+            })
+            .collect();
+
+        let start_tile = [Tile::NS, Tile::EW, Tile::NE, Tile::NW, Tile::SW, Tile::SE]
+            .into_iter()
+            .find(|tile| {
+                let connections = tile.connections(start);
+                connected.len() == 2 && connected.iter().all(|coord| connections.contains(coord))
+            })
+            .ok_or(PipeError::BadStart {
+                at: start,
+                connected: connected.clone(),
+            })?;
+
+        let tile_at = |coord: (usize, usize)| {
+            if coord == start {
+                Some(start_tile)
+            } else {
+                grid.get(&coord).copied()
+            }
+        };
+
+        let mut cells = vec![start];
+        let mut visited: HashSet<(usize, usize)> = cells.iter().copied().collect();
+        let (mut previous, mut current) = (start, connected[0]);
+        while current != start {
+            if grid[&current] == Tile::Start || !visited.insert(current) {
+                return Err(PipeError::Branch { at: current });
+            }
+            cells.push(current);
+
+            let next = grid[&current]
+                .connections(current)
+                .into_iter()
+                .find(|coord| *coord != previous)
+                .filter(|coord| *coord != current)
+                .filter(|coord| {
+                    tile_at(*coord).is_some_and(|tile| tile.connections(*coord).contains(&current))
+                })
+                .ok_or(PipeError::DeadEnd { at: current })?;
+
+            (previous, current) = (current, next);
+        }
+
+        Ok(PipeLoop { cells, start_tile })
+    }
+
+    /// Number of pipes in the loop
+    pub fn length(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The point furthest from S along the loop, and how many steps away it
+    /// is. Going either way is the same distance.
+    pub fn farthest(&self) -> ((usize, usize), usize) {
+        let steps = self.length() / 2;
+        (self.cells[steps], steps)
+    }
+}
+
+/// Ways of counting the tiles enclosed by the loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Flood fill on a scaled up grid
+    Flood,
+    /// Shoelace formula for the area and Pick's theorem for the tiles
+    Pick,
+    /// Crossing count along each row
+    Scanline,
+}
+impl FromStr for Method {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "flood" => Method::Flood,
+            "pick" => Method::Pick,
+            "scanline" => Method::Scanline,
+            _ => return Err(()),
+        })
+    }
+}
+
+pub fn enclosed(grid: &Grid, pipe_loop: &[(usize, usize)], method: Method) -> usize {
+    match method {
+        Method::Flood => flood::enclosed(grid, pipe_loop),
+        Method::Pick => area::pick(pipe_loop),
+        Method::Scanline => area::scanline(grid, pipe_loop),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    fn pipe_loop(file: &str) -> Result<PipeLoop, PipeError> {
+        PipeLoop::find(&parse_grid(fs::read_to_string(file).unwrap()))
+    }
+
+    #[test]
+    fn test_start_tile() {
+        assert_eq!(
+            pipe_loop("inputs/example1-1.txt").unwrap().start_tile,
+            Tile::SE
+        );
+        assert_eq!(
+            pipe_loop("inputs/example1-2.txt").unwrap().start_tile,
+            Tile::SE
+        );
+        assert_eq!(
+            pipe_loop("inputs/example2-3.txt").unwrap().start_tile,
+            Tile::SW
+        );
+    }
+
+    #[test]
+    fn test_loop() {
+        let pipe_loop = pipe_loop("inputs/example1-1.txt").unwrap();
+        assert_eq!(pipe_loop.length(), 8);
+        assert_eq!(&pipe_loop.cells[..3], &[(1, 1), (2, 1), (3, 1)]);
+        assert_eq!(pipe_loop.farthest(), ((3, 3), 4));
+    }
+
+    #[test]
+    fn test_bad_starts() {
+        let find = |input: &str| PipeLoop::find(&parse_grid(input.replace(' ', "\n")));
+
+        assert_eq!(find("... .|. ..."), Err(PipeError::NoStart));
+        assert_eq!(
+            find("... .S. ..."),
+            Err(PipeError::BadStart {
+                at: (1, 1),
+                connected: vec![]
+            })
+        );
+        assert_eq!(
+            find("... .S- ..."),
+            Err(PipeError::BadStart {
+                at: (1, 1),
+                connected: vec![(1, 2)]
+            })
+        );
+        assert_eq!(
+            find(".|. -S- .|."),
+            Err(PipeError::BadStart {
+                at: (1, 1),
+                connected: vec![(0, 1), (2, 1), (1, 0), (1, 2)]
+            })
+        );
+        // Nothing above S at the edge
+        assert_eq!(
+            find("-S7 .LJ"),
+            Err(PipeError::BadStart {
+                at: (0, 1),
+                connected: vec![(1, 1), (0, 0), (0, 2)]
+            })
+        );
+        let corner = find("S7 LJ").unwrap();
+        assert_eq!(corner.start_tile, Tile::SE);
+        assert_eq!(corner.length(), 4);
+    }
+
+    #[test]
+    fn test_broken_loops() {
+        let find = |input: &str| PipeLoop::find(&parse_grid(input.replace(' ', "\n")));
+
+        // Leads into the ground, off the grid, or into a pipe facing away
+        assert_eq!(find("S-7 |.. L-J"), Err(PipeError::DeadEnd { at: (2, 2) }));
+        assert_eq!(find("S-7 |.| L--"), Err(PipeError::DeadEnd { at: (2, 2) }));
+        assert_eq!(find("S-7 |.- L-J"), Err(PipeError::DeadEnd { at: (2, 2) }));
+        // A second S could go anywhere
+        assert_eq!(find("S-7 |.S L-J"), Err(PipeError::Branch { at: (1, 2) }));
+        assert_eq!(
+            find("S-7 |.. L-J").unwrap_err().to_string(),
+            "the loop comes to a dead end at (2, 2)"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use day_11::{Expansion, GalaxyMap};
+use std::{env, fs, str::FromStr};
+
+const USAGE: &str =
+    "usage: query <1 | 2 | weights file> <total | distance <a> <b> | nearest | farthest>";
+
+fn main() {
+    let input = fs::read_to_string("inputs/input.txt").unwrap();
+    let map = GalaxyMap::from_str(&input).unwrap();
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let expansion = match args.first().map(String::as_str) {
+        Some("1") => Expansion::doubled(),
+        Some("2") => Expansion::million(),
+        Some(file) => Expansion::from_str(&fs::read_to_string(file).unwrap()).unwrap(),
+        None => return println!("{}", USAGE),
+    };
+    let universe = map.expanded(&expansion);
+    let galaxy = |index: usize| {
+        args.get(index)
+            .and_then(|arg| arg.parse::<usize>().ok())
+            .filter(|galaxy| *galaxy < universe.galaxies.len())
+    };
+
+    match args.get(1).map(String::as_str) {
+        Some("total") => println!("{}", universe.total_distance()),
+        Some("distance") => match (galaxy(2), galaxy(3)) {
+            (Some(a), Some(b)) => println!("{}", universe.distance(a, b)),
+            _ => println!("{}", USAGE),
+        },
+        Some("nearest") => {
+            for (a, nearest) in universe.nearest().into_iter().enumerate() {
+                if let Some((b, distance)) = nearest {
+                    println!("{} -> {}: {}", a, b, distance);
+                }
+            }
+        }
+        Some("farthest") => {
+            if let Some((a, b, distance)) = universe.farthest_pair() {
+                println!("{} <-> {}: {}", a, b, distance);
+            }
+        }
+        _ => println!("{}", USAGE),
+    }
+}
@@ -0,0 +1,346 @@
+use std::{collections::HashMap, str::FromStr};
+
+pub type Point = (usize, usize);
+
+#[derive(Debug)]
+pub struct ParseMapError;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GalaxyMap {
+    /// Row and column of each galaxy, in reading order
+    pub galaxies: Vec<Point>,
+    /// Sorted rows without galaxies
+    pub empty_rows: Vec<usize>,
+    /// Sorted columns without galaxies
+    pub empty_columns: Vec<usize>,
+}
+impl FromStr for GalaxyMap {
+    type Err = ParseMapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut galaxies = vec![];
+        for (row, line) in s.lines().enumerate() {
+            for (col, char) in line.chars().enumerate() {
+                match char {
+                    '#' => galaxies.push((row, col)),
+                    '.' => {}
+                    _ => return Err(ParseMapError),
+                }
+            }
+        }
+
+        let rows = s.lines().count();
+        let columns = s.lines().map(|line| line.len()).max().unwrap_or_default();
+        let mut full_rows = vec![false; rows];
+        let mut full_columns = vec![false; columns];
+        for (row, col) in &galaxies {
+            full_rows[*row] = true;
+            full_columns[*col] = true;
+        }
+
+        let empty = |full: Vec<bool>| {
+            full.into_iter()
+                .enumerate()
+                .filter(|(_, full)| !full)
+                .map(|(index, _)| index)
+                .collect()
+        };
+
+        Ok(GalaxyMap {
+            galaxies,
+            empty_rows: empty(full_rows),
+            empty_columns: empty(full_columns),
+        })
+    }
+}
+impl GalaxyMap {
+    /// Where each galaxy ends up once every empty row and column has grown
+    /// to as many as its weight
+    pub fn expanded(&self, expansion: &Expansion) -> Universe {
+        let stretch = |coord: usize, empty: &[usize], weights: &[u128]| {
+            let before = empty.partition_point(|index| *index < coord);
+            coord as u128 - before as u128 + weights[before]
+        };
+        let row_weights = expansion.prefix(&self.empty_rows, &expansion.rows);
+        let column_weights = expansion.prefix(&self.empty_columns, &expansion.columns);
+
+        Universe {
+            galaxies: self
+                .galaxies
+                .iter()
+                .map(|(row, col)| {
+                    (
+                        stretch(*row, &self.empty_rows, &row_weights),
+                        stretch(*col, &self.empty_columns, &column_weights),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseExpansionError {
+    /// Line number, counting from 1
+    BadLine(usize),
+}
+
+/// How many rows or columns each empty one turns into. Rows and columns
+/// that have galaxies in them never change.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Expansion {
+    /// For every empty row and column without a weight of its own
+    pub factor: u128,
+    pub rows: HashMap<usize, u128>,
+    pub columns: HashMap<usize, u128>,
+}
+impl Expansion {
+    pub fn uniform(factor: u128) -> Self {
+        Expansion {
+            factor,
+            ..Self::default()
+        }
+    }
+
+    /// Part 1
+    pub fn doubled() -> Self {
+        Self::uniform(2)
+    }
+
+    /// Part 2
+    pub fn million() -> Self {
+        Self::uniform(1_000_000)
+    }
+
+    /// Total weight of the first n empty lines, for every n
+    fn prefix(&self, empty: &[usize], weights: &HashMap<usize, u128>) -> Vec<u128> {
+        let mut prefix = vec![0];
+        for index in empty {
+            let weight = weights.get(index).copied().unwrap_or(self.factor);
+            prefix.push(prefix.last().unwrap() + weight);
+        }
+        prefix
+    }
+}
+impl FromStr for Expansion {
+    type Err = ParseExpansionError;
+
+    /// One setting per line, such as `factor 10`, `row 3 5` or `column 7 1`.
+    /// The factor is 2 unless set.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut expansion = Expansion::uniform(2);
+
+        for (index, line) in s.lines().enumerate() {
+            let error = ParseExpansionError::BadLine(index + 1);
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let number = |word: &str| word.parse::<u128>().map_err(|_| error);
+
+            match words[..] {
+                [] => {}
+                ["factor", weight] => expansion.factor = number(weight)?,
+                ["row", row, weight] => {
+                    expansion
+                        .rows
+                        .insert(number(row)? as usize, number(weight)?);
+                }
+                ["column", column, weight] => {
+                    expansion
+                        .columns
+                        .insert(number(column)? as usize, number(weight)?);
+                }
+                _ => return Err(error),
+            }
+        }
+
+        Ok(expansion)
+    }
+}
+
+/// Galaxies after expansion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Universe {
+    /// In the same order as on the map
+    pub galaxies: Vec<(u128, u128)>,
+}
+impl Universe {
+    /// Between two galaxies by index
+    pub fn distance(&self, a: usize, b: usize) -> u128 {
+        let (a, b) = (self.galaxies[a], self.galaxies[b]);
+        a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+    }
+
+    /// Sum of the distances between every pair of galaxies
+    pub fn total_distance(&self) -> u128 {
+        let (rows, columns) = self.galaxies.iter().copied().unzip();
+        spread(rows) + spread(columns)
+    }
+
+    /// The closest other galaxy to each one and how far it is, checking
+    /// every pair
+    pub fn nearest(&self) -> Vec<Option<(usize, u128)>> {
+        (0..self.galaxies.len())
+            .map(|a| {
+                (0..self.galaxies.len())
+                    .filter(|b| *b != a)
+                    .map(|b| (b, self.distance(a, b)))
+                    .min_by_key(|(_, distance)| *distance)
+            })
+            .collect()
+    }
+
+    /// The two galaxies furthest apart and their distance. The distance is
+    /// the largest spread of either row + column or row - column.
+    pub fn farthest_pair(&self) -> Option<(usize, usize, u128)> {
+        let offset = self.galaxies.iter().map(|(_, col)| *col).max()?;
+        [false, true]
+            .into_iter()
+            .map(|flipped| {
+                let values: Vec<u128> = self
+                    .galaxies
+                    .iter()
+                    .map(|(row, col)| {
+                        if flipped {
+                            row + (offset - col)
+                        } else {
+                            row + col
+                        }
+                    })
+                    .collect();
+                let low = (0..values.len())
+                    .min_by_key(|index| values[*index])
+                    .unwrap();
+                let high = (0..values.len())
+                    .max_by_key(|index| values[*index])
+                    .unwrap();
+                (low.min(high), low.max(high), values[high] - values[low])
+            })
+            .max_by_key(|(_, _, distance)| *distance)
+    }
+}
+
+/// Sum of the differences between every pair of values. Once sorted, each
+/// value is the larger one in a pair with everything before it.
+pub fn spread(mut values: Vec<u128>) -> u128 {
+    values.sort_unstable();
+
+    let mut before = 0;
+    let mut total = 0;
+    for (index, value) in values.into_iter().enumerate() {
+        total += value * index as u128 - before;
+        before += value;
+    }
+    total
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use itertools::Itertools;
+    use std::fs;
+
+    #[test]
+    fn test_parsing() {
+        let input = fs::read_to_string("inputs/example.txt").unwrap();
+        let map = GalaxyMap::from_str(&input).unwrap();
+
+        assert_eq!(map.galaxies.len(), 9);
+        assert_eq!(map.galaxies[0], (0, 3));
+        assert_eq!(map.empty_rows, vec![3, 7]);
+        assert_eq!(map.empty_columns, vec![2, 5, 8]);
+        assert!(GalaxyMap::from_str("..\n.x").is_err());
+    }
+
+    #[test]
+    fn test_expanded() {
+        let input = fs::read_to_string("inputs/example.txt").unwrap();
+        let map = GalaxyMap::from_str(&input).unwrap();
+        let expanded = map.expanded(&Expansion::doubled()).galaxies;
+
+        // Worked through in the puzzle text
+        assert_eq!(expanded[4], (6, 1));
+        assert_eq!(expanded[8], (11, 5));
+    }
+
+    #[test]
+    fn test_against_pairs() {
+        let input = fs::read_to_string("inputs/example.txt").unwrap();
+        let map = GalaxyMap::from_str(&input).unwrap();
+
+        for factor in [0, 1, 2, 10, 100, 1_000_000_000_000] {
+            let universe = map.expanded(&Expansion::uniform(factor));
+            let pairwise: u128 = (0..universe.galaxies.len())
+                .tuple_combinations()
+                .map(|(a, b)| universe.distance(a, b))
+                .sum();
+            assert_eq!(universe.total_distance(), pairwise);
+        }
+    }
+
+    #[test]
+    fn test_weights() {
+        let input = fs::read_to_string("inputs/example.txt").unwrap();
+        let map = GalaxyMap::from_str(&input).unwrap();
+        let expansion = Expansion::from_str("factor 1\nrow 3 10\n\ncolumn 5 0\nrow 4 100").unwrap();
+        assert_eq!(expansion.factor, 1);
+
+        // Row 4 has a galaxy, so its weight does nothing
+        let universe = map.expanded(&expansion);
+        assert_eq!(universe.galaxies[0], (0, 3));
+        assert_eq!(universe.galaxies[1], (1, 6));
+        assert_eq!(universe.galaxies[3], (13, 5));
+
+        assert_eq!(
+            Expansion::from_str("factor"),
+            Err(ParseExpansionError::BadLine(1))
+        );
+        assert_eq!(
+            Expansion::from_str("row 1 2\ncol 1 2"),
+            Err(ParseExpansionError::BadLine(2))
+        );
+    }
+
+    #[test]
+    fn test_queries() {
+        let input = fs::read_to_string("inputs/example.txt").unwrap();
+        let map = GalaxyMap::from_str(&input).unwrap();
+        let universe = map.expanded(&Expansion::doubled());
+
+        // Pairs from the puzzle text, numbered from 1 there
+        assert_eq!(universe.distance(4, 8), 9);
+        assert_eq!(universe.distance(0, 6), 15);
+        assert_eq!(universe.distance(2, 5), 17);
+        assert_eq!(universe.distance(7, 8), 5);
+
+        let nearest = universe.nearest();
+        for (a, closest) in nearest.iter().enumerate() {
+            let (b, distance) = closest.unwrap();
+            assert_eq!(universe.distance(a, b), distance);
+            assert!((0..9)
+                .filter(|c| *c != a)
+                .all(|c| universe.distance(a, c) >= distance));
+        }
+
+        let (a, b, distance) = universe.farthest_pair().unwrap();
+        assert!(a < b);
+        assert_eq!(universe.distance(a, b), distance);
+        let most = (0..9)
+            .tuple_combinations()
+            .map(|(a, b)| universe.distance(a, b))
+            .max()
+            .unwrap();
+        assert_eq!(distance, most);
+
+        let lonely = Universe {
+            galaxies: vec![(3, 4)],
+        };
+        assert_eq!(lonely.nearest(), vec![None]);
+        assert_eq!(lonely.farthest_pair(), Some((0, 0, 0)));
+    }
+
+    #[test]
+    fn test_spread() {
+        assert_eq!(spread(vec![]), 0);
+        assert_eq!(spread(vec![5]), 0);
+        assert_eq!(spread(vec![3, 1, 2]), 1 + 2 + 1);
+    }
+}
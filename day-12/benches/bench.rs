@@ -1,5 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use day_12::line_permutations;
+use day_12::{compute, line_permutations};
+use std::fs;
 
 const EASY_LINE: &str = "???.### 1,1,3";
 const MEDIUM_LINE: &str = ".??..??...?##. 1,1,3";
@@ -23,6 +24,13 @@ pub fn criterion_benchmark(c: &mut Criterion) {
             line_permutations(black_box(HARD_LINE));
         })
     });
+
+    let input = fs::read_to_string("inputs/input.txt").unwrap();
+    c.bench_function("Whole input", |b| {
+        b.iter(|| {
+            compute(black_box(input.clone()));
+        })
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -38,7 +38,7 @@ fn line_permutations(input: &str) -> usize {
         .into_iter()
         .filter(|line| {
             // Form the streaks or broken
-            line.into_iter()
+            line.iter()
                 .fold(vec![0], |mut acc, sym| {
                     if *sym == Symbol::Broken {
                         let last = acc.last_mut().unwrap();
@@ -65,7 +65,7 @@ fn line_permutations(input: &str) -> usize {
 fn brute_force(map: &str) -> Vec<Vec<Symbol>> {
     // Top level is bundle, second level is variation, third level is symbols
     map.chars()
-        .map(|char| Symbol::from(char))
+        .map(Symbol::from)
         .fold(vec![vec![]], |branches, sym| {
             branches
                 .into_iter()
@@ -89,7 +89,7 @@ fn brute_force(map: &str) -> Vec<Vec<Symbol>> {
 fn compute(input: String) -> String {
     input
         .lines()
-        .map(|line| line_permutations(line))
+        .map(line_permutations)
         .sum::<usize>()
         .to_string()
 }
@@ -116,7 +116,7 @@ mod test {
 
     #[test]
     fn example_lines() {
-        for (line, expected) in vec![
+        for (line, expected) in [
             ("???.### 1,1,3", 1),
             (".??..??...?##. 1,1,3", 4),
             ("?###???????? 3,2,1", 10),
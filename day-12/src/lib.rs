@@ -4,60 +4,66 @@ use std::iter;
 pub fn line_permutations(input: &str) -> usize {
     let (map, digits) = input.split_once(" ").unwrap();
 
-    let requirements: Vec<usize> = iter::repeat(digits)
-        .take(5)
+    let requirements: Vec<usize> = iter::repeat_n(digits, 5)
         .join(",")
         .split(",")
         .map(|num| num.parse::<usize>().unwrap())
         .collect();
 
-    let input = iter::repeat(map).take(5).join("?");
+    let input = iter::repeat_n(map, 5).join("?");
 
-    recurse(requirements, input)
+    arrangements(&input, &requirements)
 }
 
-#[memoize::memoize]
-fn recurse(req: Vec<usize>, input: String) -> usize {
-    if req.is_empty() {
-        return !input.contains('#') as usize;
-    } else if input.is_empty() {
-        return 0; // Still requests to go and no input
+/// Ways to fill in the unknowns of `springs` so that the runs of broken
+/// springs match `groups`. One table of (position, group) counts per call
+/// and nothing allocated after that.
+fn arrangements(springs: &str, groups: &[usize]) -> usize {
+    let springs = springs.as_bytes();
+    let (len, width) = (springs.len(), groups.len() + 1);
+
+    // Length of the stretch without operational springs from each position
+    let mut runs = vec![0; len + 1];
+    for pos in (0..len).rev() {
+        if springs[pos] != b'.' {
+            runs[pos] = runs[pos + 1] + 1;
+        }
     }
 
-    if input.len() < req.iter().sum::<usize>() + req.len() - 1 {
-        return 0;
+    // ways[pos * width + group] is how many ways groups[group..] fit into
+    // springs[pos..]
+    let mut ways = vec![0; (len + 1) * width];
+    ways[len * width + groups.len()] = 1;
+
+    for pos in (0..len).rev() {
+        for group in 0..width {
+            let mut count = 0;
+
+            if springs[pos] != b'#' {
+                count += ways[(pos + 1) * width + group];
+            }
+
+            if springs[pos] != b'.' && group < groups.len() {
+                let end = pos + groups[group];
+                if runs[pos] >= groups[group] && (end == len || springs[end] != b'#') {
+                    // Skip over the spring after the group too, it has to be
+                    // operational
+                    let next = (end + 1).min(len);
+                    count += ways[next * width + group + 1];
+                }
+            }
+
+            ways[pos * width + group] = count;
+        }
     }
 
-    let mut chars = input.chars();
-
-    match chars.next().unwrap() {
-        '#' => hash_recurse(req, input),
-        '.' => recurse(req, chars.collect()),
-        '?' => recurse(req.clone(), chars.collect()) + hash_recurse(req, input),
-        _ => panic!("Invalid character"),
-    }
-}
-
-fn hash_recurse(req: Vec<usize>, input: String) -> usize {
-    let first_req = req.iter().next().unwrap();
-    let chunk = input.chars().take(*first_req).collect_vec();
-
-    if !chunk.contains(&'.') && input.chars().skip(*first_req).next() != Some('#') {
-        let start_index = *first_req + 1;
-
-        recurse(
-            req.into_iter().skip(1).collect(),
-            input.chars().skip(start_index).collect(),
-        )
-    } else {
-        0
-    }
+    ways[0]
 }
 
 pub fn compute(input: String) -> String {
     input
         .lines()
-        .map(|line| line_permutations(line))
+        .map(line_permutations)
         .sum::<usize>()
         .to_string()
 }
@@ -80,38 +86,89 @@ mod test {
             .unwrap()
             .lines()
         {
-            assert_eq!(line_permutations(dbg!(line)), 1);
+            assert_eq!(line_permutations(line), 1, "{}", line);
         }
     }
 
     #[test]
     fn example_lines() {
-        for (line, expected) in vec![
+        for (line, expected) in [
             ("???.### 1,1,3", 1),
-            (".??..??...?##. 1,1,3", 16384), // Slow
+            (".??..??...?##. 1,1,3", 16384),
             ("?#?#?#?#?#?#?#? 1,3,1,6", 1),
-            ("????.#...#... 4,1,1", 16), // Slow
+            ("????.#...#... 4,1,1", 16),
             ("????.######..#####. 1,6,5", 2500),
-            ("?###???????? 3,2,1", 506250), // Mega slow
+            ("?###???????? 3,2,1", 506250),
         ] {
-            assert_eq!(line_permutations(dbg!(line)), expected);
+            assert_eq!(line_permutations(line), expected, "{}", line);
         }
     }
 
     #[test]
-    fn test_recurse() {
-        for (input, requests, expected) in vec![
-            ("".into(), vec![], 1),
-            (".".into(), vec![], 1),
-            ("?".into(), vec![], 1),
-            ("#".into(), vec![], 0),
-            ("#".into(), vec![1], 1),
-            ("##".into(), vec![2], 1),
-            ("#?".into(), vec![2], 1),
-            ("#?#".into(), vec![3], 1),
-            ("#?#".into(), vec![2], 0),
+    fn test_arrangements() {
+        for (input, requests, expected) in [
+            ("", vec![], 1),
+            (".", vec![], 1),
+            ("?", vec![], 1),
+            ("#", vec![], 0),
+            ("#", vec![1], 1),
+            ("##", vec![2], 1),
+            ("#?", vec![2], 1),
+            ("#?#", vec![3], 1),
+            ("#?#", vec![2], 0),
+            ("", vec![1], 0),
+            ("???", vec![1, 1], 1),
+            ("????", vec![1, 1], 3),
         ] {
-            assert_eq!(recurse(dbg!(requests), dbg!(input)), expected)
+            assert_eq!(
+                arrangements(input, &requests),
+                expected,
+                "{} {:?}",
+                input,
+                requests
+            );
+        }
+    }
+
+    /// Every way of filling in the unknowns, checked one by one
+    fn brute_force(springs: &str, groups: &[usize]) -> usize {
+        let unknowns: Vec<usize> = springs
+            .char_indices()
+            .filter(|(_, c)| *c == '?')
+            .map(|(index, _)| index)
+            .collect();
+
+        (0..1usize << unknowns.len())
+            .filter(|mask| {
+                let mut filled: Vec<char> = springs.chars().collect();
+                for (bit, index) in unknowns.iter().enumerate() {
+                    filled[*index] = if mask >> bit & 1 == 1 { '#' } else { '.' };
+                }
+                let runs: Vec<usize> = filled
+                    .split(|c| *c == '.')
+                    .map(|run| run.len())
+                    .filter(|len| *len > 0)
+                    .collect();
+                runs == groups
+            })
+            .count()
+    }
+
+    #[test]
+    fn against_brute_force() {
+        for line in fs::read_to_string("inputs/input.txt")
+            .unwrap()
+            .lines()
+            .take(100)
+        {
+            let (springs, groups) = line.split_once(' ').unwrap();
+            let groups: Vec<usize> = groups.split(',').map(|num| num.parse().unwrap()).collect();
+            assert_eq!(
+                arrangements(springs, &groups),
+                brute_force(springs, &groups),
+                "{}",
+                line
+            );
         }
     }
 }